@@ -0,0 +1,302 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use super::sql_entities::{SqlERData, Table, TableColumn, TableIndex};
+use crate::{GeneratorConfigOptions, ViewGenerator};
+use serde::Serialize;
+use tinytemplate::{format_unescaped, TinyTemplate};
+
+// DBML is an exchange format (https://dbml.dbdiagram.io/docs), so everything
+// from SqlERData is exported and diagram options are ignored.
+// DBML doesn't support views, they are skipped.
+static DBML_TEMPLATE: &str = "{{ for en in enums }}{en}\n{{ endfor }}\
+    {{ for tbl in tables }}{tbl}\n{{ endfor }}\
+    {{ for fk in foreign_keys }}{fk}{{ endfor }}";
+
+static TABLE_TEMPLATE: &str = "Table {name} \\{\n\
+    {{ for col in columns }}  {col}\n{{ endfor }}\
+    {{ if indexes }}\n  indexes \\{\n{{ for idx in indexes }}    {idx}\n{{ endfor }}  }\n{{ endif }}\
+    {{ if comment }}\n  Note: {comment}\n{{ endif }}}\n";
+
+static ENUM_TEMPLATE: &str = "Enum {name} \\{\n{{ for v in values }}  {v}\n{{ endfor }}}\n";
+
+static REL_TEMPLATE: &str =
+    "Ref: {source} {{ if is_zero_one_to_one }}-{{ else }}>{{ endif }} {target}\n";
+
+#[derive(Serialize)]
+struct SDbml {
+    enums: Vec<String>,
+    tables: Vec<String>,
+    foreign_keys: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct STable {
+    name: String,
+    columns: Vec<String>,
+    indexes: Vec<String>,
+    comment: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SEnum {
+    name: String,
+    values: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct SForeignKey {
+    source: String, // Ex: order_detail.(id, customer_order_id)
+    target: String,
+    is_zero_one_to_one: bool,
+}
+
+// Identifiers with special characters must be quoted
+fn ident(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_simple = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_simple {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\\\""))
+    }
+}
+
+// Qualified as `schema.table` if several schemas are loaded
+fn table_ident(tbl: &Table) -> String {
+    match &tbl.schema {
+        Some(schema) => match tbl.name.strip_prefix(&format!("{schema}.")) {
+            Some(name) => format!("{}.{}", ident(schema), ident(name)),
+            None => ident(&tbl.name),
+        },
+        None => ident(&tbl.name),
+    }
+}
+
+// Enums are qualified as `schema.enum` if several schemas are loaded,
+// columns refer to them the same way
+fn enum_ident(name: &str) -> String {
+    match name.split_once('.') {
+        Some((schema, name)) => format!("{}.{}", ident(schema), ident(name)),
+        None => ident(name),
+    }
+}
+
+fn string(s: &str) -> String {
+    format!(
+        "'{}'",
+        s.replace('\\', "\\\\")
+            .replace('\'', "\\'")
+            .replace('\n', "\\n")
+    )
+}
+
+// Numbers are written as is, everything else is an expression
+fn default_value(value: &str) -> String {
+    if value.parse::<f64>().is_ok() || ["true", "false", "null"].contains(&value) {
+        value.to_string()
+    } else {
+        format!("`{value}`")
+    }
+}
+
+fn datatype(datatype: &str) -> String {
+    if datatype.contains(' ') {
+        format!("\"{datatype}\"")
+    } else {
+        datatype.to_string()
+    }
+}
+
+// Ex: customer_id ref column or (customer_id, ordered_at) for composite ones
+fn columns_ref(tbl: &Table, columns: &[Arc<TableColumn>]) -> String {
+    let columns: Vec<String> = columns.iter().map(|col| ident(&col.name)).collect();
+    if columns.len() == 1 {
+        format!("{}.{}", table_ident(tbl), columns[0])
+    } else {
+        format!("{}.({})", table_ident(tbl), columns.join(", "))
+    }
+}
+
+pub struct DbmlGenerator<'a> {
+    str_templates: TinyTemplate<'a>,
+}
+
+impl<'a> DbmlGenerator<'a> {
+    pub fn new() -> Result<DbmlGenerator<'a>, crate::SqlantError> {
+        let mut str_templates = TinyTemplate::new();
+        str_templates.add_template("dbml", DBML_TEMPLATE)?;
+        str_templates.add_template("table", TABLE_TEMPLATE)?;
+        str_templates.add_template("enum", ENUM_TEMPLATE)?;
+        str_templates.add_template("rel", REL_TEMPLATE)?;
+        str_templates.set_default_formatter(&format_unescaped);
+        Ok(DbmlGenerator { str_templates })
+    }
+
+    // Ex: id bigint [pk, not null, default: `nextval('customer_id_seq'::regclass)`]
+    fn column_render(tbl: &Table, col: &TableColumn) -> String {
+        let mut settings = vec![];
+        // Composite PK is rendered in indexes block
+        if col.is_pk() && !tbl.has_composite_pk {
+            settings.push("pk".to_string());
+        } else {
+            if col.is_nn() {
+                settings.push("not null".to_string());
+            }
+            // Columns of partial unique indexes are marked unique too, so it's
+            // taken from indexes if loader provides them
+            let is_unique = if tbl.indexes.is_empty() {
+                col.is_unique()
+            } else {
+                tbl.indexes
+                    .iter()
+                    .any(|idx| Self::is_unique_column(tbl, idx) && idx.columns[0] == col.name)
+            };
+            if is_unique {
+                settings.push("unique".to_string());
+            }
+        }
+        if let Some(default) = col.default_value() {
+            settings.push(format!("default: {}", default_value(default)));
+        }
+        if let Some(comment) = &col.comment {
+            settings.push(format!("note: {}", string(comment)));
+        }
+        let mut res = format!("{} {}", ident(&col.name), datatype(&col.datatype));
+        if !settings.is_empty() {
+            res += &format!(" [{}]", settings.join(", "));
+        }
+        res
+    }
+
+    fn is_unique_column(tbl: &Table, idx: &TableIndex) -> bool {
+        idx.unique
+            && idx.predicate.is_none()
+            && idx.columns.len() == 1
+            && tbl.columns.iter().any(|col| col.name == idx.columns[0])
+    }
+
+    // Ex: (customer_id, `lower(name)`) [name: 'customer_idx', unique, type: btree]
+    fn index_render(tbl: &Table, idx: &TableIndex) -> String {
+        let columns: Vec<String> = idx
+            .columns
+            .iter()
+            .map(|col| {
+                if tbl.columns.iter().any(|c| &c.name == col) {
+                    ident(col)
+                } else {
+                    format!("`{col}`")
+                }
+            })
+            .collect();
+        let mut settings = vec![format!("name: {}", string(&idx.name))];
+        if idx.unique {
+            settings.push("unique".into());
+        }
+        // Only btree (default) and hash index types are supported by DBML
+        if idx.method == "hash" {
+            settings.push("type: hash".into());
+        }
+        if let Some(predicate) = &idx.predicate {
+            settings.push(format!("note: {}", string(&format!("WHERE {predicate}"))));
+        }
+        format!("({}) [{}]", columns.join(", "), settings.join(", "))
+    }
+
+    fn table_render(&self, tbl: &Table) -> Result<String, crate::SqlantError> {
+        // Keep order of columns from database
+        let mut columns = tbl.columns.clone();
+        columns.sort_by_key(|col| col.col_num);
+        let pks: Vec<&str> = columns
+            .iter()
+            .filter(|col| col.is_pk())
+            .map(|col| col.name.as_str())
+            .collect();
+        let mut indexes = vec![];
+        if tbl.has_composite_pk {
+            let pks: Vec<String> = pks.iter().map(|name| ident(name)).collect();
+            indexes.push(format!("({}) [pk]", pks.join(", ")));
+        }
+        // PK and single column UNIQUE indexes are rendered as column settings
+        indexes.extend(
+            tbl.indexes
+                .iter()
+                .filter(|idx| {
+                    let is_pk = idx.unique
+                        && idx
+                            .columns
+                            .iter()
+                            .map(String::as_str)
+                            .collect::<BTreeSet<_>>()
+                            == pks.iter().copied().collect();
+                    !is_pk && !Self::is_unique_column(tbl, idx)
+                })
+                .map(|idx| Self::index_render(tbl, idx)),
+        );
+        Ok(self.str_templates.render(
+            "table",
+            &STable {
+                name: table_ident(tbl),
+                columns: columns
+                    .iter()
+                    .map(|col| Self::column_render(tbl, col))
+                    .collect(),
+                indexes,
+                comment: tbl.comment.as_deref().map(string),
+            },
+        )?)
+    }
+}
+
+impl ViewGenerator for DbmlGenerator<'_> {
+    fn generate(
+        &self,
+        sql_erd: SqlERData,
+        _opts: &GeneratorConfigOptions,
+    ) -> Result<String, crate::SqlantError> {
+        let enums: Vec<String> = sql_erd
+            .enums
+            .iter()
+            .map(|(name, values)| {
+                self.str_templates.render(
+                    "enum",
+                    &SEnum {
+                        name: enum_ident(name),
+                        values: values.iter().map(|v| ident(v)).collect(),
+                    },
+                )
+            })
+            .collect::<Result<Vec<String>, _>>()?;
+        let tables: Vec<String> = sql_erd
+            .tables
+            .iter()
+            .map(|tbl| self.table_render(tbl))
+            .collect::<Result<Vec<String>, _>>()?;
+        let foreign_keys: Vec<String> = sql_erd
+            .foreign_keys
+            .iter()
+            .map(|fk| {
+                self.str_templates.render(
+                    "rel",
+                    &SForeignKey {
+                        source: columns_ref(&fk.source_table, &fk.source_columns),
+                        target: columns_ref(&fk.target_table, &fk.target_columns),
+                        is_zero_one_to_one: fk.is_zero_one_to_one,
+                    },
+                )
+            })
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(self.str_templates.render(
+            "dbml",
+            &SDbml {
+                enums,
+                tables,
+                foreign_keys,
+            },
+        )?)
+    }
+}
@@ -0,0 +1,1184 @@
+use sqlparser::{
+    ast::{
+        visit_expressions, visit_expressions_mut, visit_relations, AlterColumnOperation,
+        AlterTableOperation, ArrayElemTypeDef, ColumnOption, CommentObject,
+        ConstraintCharacteristics, CreateIndex, CreateTable, DataType, DeferrableInitial, Expr,
+        FunctionArg, FunctionArgExpr, FunctionArguments, Ident, ObjectName, ObjectType, Query,
+        SelectItem, SetExpr, Statement, TableConstraint, TableFactor, TimezoneInfo,
+        UserDefinedTypeRepresentation, Value,
+    },
+    dialect::PostgreSqlDialect,
+    keywords::Keyword,
+    parser::Parser,
+    tokenizer::Token,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::ControlFlow,
+    path::Path,
+    sync::Arc,
+};
+
+use crate::{
+    sql_entities::{
+        ColumnConstraints, ForeignKey, ReferentialAction, SqlERData, SqlERDataLoader, Table,
+        TableColumn, TableIndex, View,
+    },
+    SqlantError,
+};
+
+/// Internal type of Column. With values that parsed from DDL
+#[derive(Debug)]
+struct ColumnInternal {
+    name: String,
+    datatype: String,
+    constraints: BTreeSet<ColumnConstraints>,
+}
+
+/// (schema_name, name)
+type QualifiedName = (String, String);
+
+/// Internal type of Foreign Key. With values that parsed from DDL
+#[derive(Debug)]
+struct FkInternal {
+    source_table_name: QualifiedName,
+    source_columns: Vec<String>,
+    target_table_name: QualifiedName,
+    // Empty vector means that FK references PK of target table
+    target_columns: Vec<String>,
+    name: String,
+    on_delete: ReferentialAction,
+    on_update: ReferentialAction,
+    // (DEFERRABLE, INITIALLY DEFERRED). MATCH clause isn't supported by parser
+    deferrable: (bool, bool),
+}
+
+#[derive(Debug)]
+struct ViewInternal {
+    name: QualifiedName,
+    materialized: bool,
+    columns: Vec<ColumnInternal>,
+    // Tables and views that are used by query of view (may be not created)
+    depends_on: BTreeSet<QualifiedName>,
+}
+
+/// Loads ERD data from SQL DDL statements (migrations, `pg_dump --schema-only` output)
+/// without connecting to a database.
+/// Statements that aren't related to ERD are skipped,
+/// unparseable `CREATE TABLE` and `ALTER TABLE` statements are reported as errors.
+pub struct DdlERDLoader {
+    sql: String,
+    schema_names: Vec<String>,
+    qualify_names: bool,
+    // Keeps order of creation
+    tables: Vec<(QualifiedName, Vec<ColumnInternal>)>,
+    fks: Vec<FkInternal>,
+    enums: BTreeMap<QualifiedName, Vec<String>>,
+    views: Vec<ViewInternal>,
+    indexes: Vec<(QualifiedName, TableIndex)>, // key - table
+    // key - (table or view, column name). Column is None for comment of table itself
+    comments: BTreeMap<(QualifiedName, Option<String>), String>,
+}
+
+impl DdlERDLoader {
+    /// Only objects of `schema_names` are loaded, `*` means all schemas.
+    /// Unqualified objects belong to the first schema (`public` for `*`)
+    pub fn new(sql: String, schema_names: Vec<String>) -> DdlERDLoader {
+        DdlERDLoader {
+            sql,
+            schema_names,
+            qualify_names: false,
+            tables: vec![],
+            fks: vec![],
+            enums: BTreeMap::new(),
+            views: vec![],
+            indexes: vec![],
+            comments: BTreeMap::new(),
+        }
+    }
+
+    /// Files are concatenated in the given order (e.g. migrations)
+    pub fn from_files<P: AsRef<Path>>(
+        paths: &[P],
+        schema_names: Vec<String>,
+    ) -> Result<DdlERDLoader, SqlantError> {
+        let mut sql = String::new();
+        for path in paths {
+            let path = path.as_ref();
+            let content = std::fs::read_to_string(path).map_err(SqlantError::io(path))?;
+            sql.push_str(&content);
+            // File can end without semicolon
+            sql.push_str("\n;\n");
+        }
+        Ok(Self::new(sql, schema_names))
+    }
+
+    fn is_all_schemas(&self) -> bool {
+        self.schema_names.iter().any(|s| s == "*")
+    }
+
+    fn default_schema(&self) -> &str {
+        match self.schema_names.first() {
+            Some(schema_name) if schema_name != "*" => schema_name,
+            _ => "public",
+        }
+    }
+
+    /// Returns qualified name of object if it belongs to loaded schemas
+    fn object_name(&self, name: &ObjectName) -> Option<QualifiedName> {
+        let (last, qualifiers) = name.0.split_last()?;
+        let schema_name = qualifiers
+            .last()
+            .map_or(self.default_schema().to_string(), ident_name);
+        if !self.is_all_schemas() && !self.schema_names.contains(&schema_name) {
+            return None;
+        }
+        Some((schema_name, ident_name(last)))
+    }
+
+    /// Expression the same way as PostgreSQL shows it in catalog.
+    /// Ex: (nextval('public.seq'::REGCLASS)) -> nextval('seq'::regclass)
+    fn expression(&self, expr: &Expr) -> String {
+        let mut expr = expr.clone();
+        let _ = visit_expressions_mut(&mut expr, |e| {
+            if let Expr::Cast {
+                expr, data_type, ..
+            } = e
+            {
+                if let (DataType::Regclass, Expr::Value(Value::SingleQuotedString(name))) =
+                    (&*data_type, expr.as_mut())
+                {
+                    if let Some(unqualified) = name
+                        .strip_prefix(self.default_schema())
+                        .and_then(|n| n.strip_prefix('.'))
+                    {
+                        *name = unqualified.to_string();
+                    }
+                }
+                let type_name = match &*data_type {
+                    DataType::Custom(name, _) => self
+                        .object_name(name)
+                        .filter(|(schema_name, _)| schema_name != self.default_schema())
+                        .map(|(schema_name, name)| format!("{schema_name}.{name}")),
+                    _ => None,
+                };
+                *data_type = DataType::Custom(
+                    ObjectName(vec![Ident::new(
+                        type_name.unwrap_or_else(|| normalize_datatype(data_type)),
+                    )]),
+                    vec![],
+                );
+            }
+            ControlFlow::<()>::Continue(())
+        });
+        // PostgreSQL wraps CHECK, DEFAULT and index predicates into parentheses
+        while let Expr::Nested(inner) = expr {
+            expr = *inner;
+        }
+        expr.to_string()
+    }
+
+    fn parse_statements(&self) -> Result<Vec<Statement>, SqlantError> {
+        // psql meta-commands (e.g. \restrict in pg_dump output) aren't SQL
+        let sql: String = self
+            .sql
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('\\'))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let dialect = PostgreSqlDialect {};
+        let mut parser =
+            Parser::new(&dialect)
+                .try_with_sql(&sql)
+                .map_err(|e| SqlantError::Parse {
+                    input: "SQL".to_string(),
+                    message: e.to_string(),
+                })?;
+
+        let mut res = vec![];
+        loop {
+            while parser.consume_token(&Token::SemiColon) {}
+            if parser.peek_token().token == Token::EOF {
+                break;
+            }
+            let kind = table_statement_kind(&parser.peek_tokens::<4>());
+            match (parser.parse_statement(), kind) {
+                (Ok(statement), _) => res.push(statement),
+                // Tables are the model, so they can't be skipped
+                (Err(e), Some(kind)) => {
+                    return Err(SqlantError::Parse {
+                        input: format!("{kind} statement"),
+                        message: e.to_string(),
+                    })
+                }
+                (Err(_), None) => {
+                    // Skip the rest of unsupported statement (functions, triggers, grants, ...)
+                    loop {
+                        let token = parser.next_token().token;
+                        if token == Token::SemiColon || token == Token::EOF {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    fn table_mut(&mut self, table_name: &QualifiedName) -> Option<&mut Vec<ColumnInternal>> {
+        self.tables
+            .iter_mut()
+            .find(|(name, _)| name == table_name)
+            .map(|(_, columns)| columns)
+    }
+
+    fn add_column_constraint(
+        &mut self,
+        table_name: &QualifiedName,
+        column_names: &[String],
+        constraint: ColumnConstraints,
+    ) {
+        if let Some(columns) = self.table_mut(table_name) {
+            for col in columns
+                .iter_mut()
+                .filter(|col| column_names.contains(&col.name))
+            {
+                col.constraints.insert(constraint.clone());
+            }
+        }
+    }
+
+    fn remove_column_constraint(
+        &mut self,
+        table_name: &QualifiedName,
+        column_name: &str,
+        predicate: impl Fn(&ColumnConstraints) -> bool,
+    ) {
+        if let Some(col) = self
+            .table_mut(table_name)
+            .and_then(|columns| columns.iter_mut().find(|col| col.name == column_name))
+        {
+            col.constraints.retain(|c| !predicate(c));
+        }
+    }
+
+    fn column_def(
+        &mut self,
+        table_name: &QualifiedName,
+        column: &sqlparser::ast::ColumnDef,
+    ) -> ColumnInternal {
+        let mut constraints = BTreeSet::new();
+        if is_serial(&column.data_type) {
+            // The same default as PostgreSQL creates for serial types
+            constraints.insert(ColumnConstraints::Default(format!(
+                "nextval('{}_{}_seq'::regclass)",
+                table_name.1,
+                ident_name(&column.name)
+            )));
+        }
+        for option in &column.options {
+            match &option.option {
+                ColumnOption::NotNull => {
+                    constraints.insert(ColumnConstraints::NotNull);
+                }
+                ColumnOption::Default(expr) => {
+                    constraints.insert(ColumnConstraints::Default(self.expression(expr)));
+                }
+                ColumnOption::Unique { is_primary, .. } => {
+                    if *is_primary {
+                        // The PRIMARY KEY of a table is a combination of NOT NULL and UNIQUE constraint.
+                        constraints.insert(ColumnConstraints::PrimaryKey);
+                        constraints.insert(ColumnConstraints::NotNull);
+                    }
+                    constraints.insert(ColumnConstraints::Unique);
+                    self.constraint_index(
+                        table_name,
+                        option.name.as_ref(),
+                        vec![ident_name(&column.name)],
+                        *is_primary,
+                    );
+                }
+                ColumnOption::ForeignKey {
+                    foreign_table,
+                    referred_columns,
+                    on_delete,
+                    on_update,
+                    characteristics,
+                } => {
+                    if let Some(target_table_name) = self.object_name(foreign_table) {
+                        let source_columns = vec![ident_name(&column.name)];
+                        self.fks.push(FkInternal {
+                            name: fk_name(table_name, option.name.as_ref(), &source_columns),
+                            source_table_name: table_name.clone(),
+                            source_columns,
+                            target_table_name,
+                            target_columns: referred_columns.iter().map(ident_name).collect(),
+                            on_delete: referential_action(on_delete.as_ref()),
+                            on_update: referential_action(on_update.as_ref()),
+                            deferrable: deferrable(characteristics.as_ref()),
+                        });
+                    }
+                }
+                ColumnOption::Check(expr) => {
+                    constraints.insert(ColumnConstraints::Check(self.expression(expr)));
+                }
+                _ => {}
+            }
+        }
+        // Enums are qualified until it's known whether names of several schemas are loaded
+        let datatype = match &column.data_type {
+            DataType::Custom(name, _) => self
+                .object_name(name)
+                .filter(|name| self.enums.contains_key(name))
+                .map(|(schema_name, name)| format!("{schema_name}.{name}")),
+            _ => None,
+        };
+        ColumnInternal {
+            name: ident_name(&column.name),
+            datatype: datatype.unwrap_or_else(|| normalize_datatype(&column.data_type)),
+            constraints,
+        }
+    }
+
+    fn table_constraint(&mut self, table_name: &QualifiedName, constraint: &TableConstraint) {
+        let idents = |columns: &[sqlparser::ast::Ident]| -> Vec<String> {
+            columns.iter().map(ident_name).collect()
+        };
+        match constraint {
+            TableConstraint::PrimaryKey { name, columns, .. } => {
+                let columns = idents(columns);
+                for constraint in [
+                    ColumnConstraints::PrimaryKey,
+                    ColumnConstraints::NotNull,
+                    ColumnConstraints::Unique,
+                ] {
+                    self.add_column_constraint(table_name, &columns, constraint);
+                }
+                self.constraint_index(table_name, name.as_ref(), columns, true);
+            }
+            TableConstraint::Unique { name, columns, .. } => {
+                let columns = idents(columns);
+                // Columns of multi-column UNIQUE aren't unique by themselves
+                if columns.len() == 1 {
+                    self.add_column_constraint(table_name, &columns, ColumnConstraints::Unique);
+                }
+                self.constraint_index(table_name, name.as_ref(), columns, false);
+            }
+            TableConstraint::ForeignKey {
+                name,
+                columns,
+                foreign_table,
+                referred_columns,
+                on_delete,
+                on_update,
+                characteristics,
+            } => {
+                if let Some(target_table_name) = self.object_name(foreign_table) {
+                    let source_columns = idents(columns);
+                    self.fks.push(FkInternal {
+                        name: fk_name(table_name, name.as_ref(), &source_columns),
+                        source_table_name: table_name.clone(),
+                        source_columns,
+                        target_table_name,
+                        target_columns: idents(referred_columns),
+                        on_delete: referential_action(on_delete.as_ref()),
+                        on_update: referential_action(on_update.as_ref()),
+                        deferrable: deferrable(characteristics.as_ref()),
+                    });
+                }
+            }
+            TableConstraint::Check { expr, .. } => {
+                // Table CHECK is attached to every column it mentions
+                let mut columns = vec![];
+                let _ = visit_expressions(expr.as_ref(), |e| {
+                    match e {
+                        Expr::Identifier(ident) => columns.push(ident_name(ident)),
+                        Expr::CompoundIdentifier(idents) => {
+                            if let Some(ident) = idents.last() {
+                                columns.push(ident_name(ident));
+                            }
+                        }
+                        _ => {}
+                    }
+                    ControlFlow::<()>::Continue(())
+                });
+                self.add_column_constraint(
+                    table_name,
+                    &columns,
+                    ColumnConstraints::Check(self.expression(expr)),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn create_table(&mut self, create_table: &CreateTable) {
+        let Some(table_name) = self.object_name(&create_table.name) else {
+            return;
+        };
+        // Existing table (with its later ALTERs) is kept by IF NOT EXISTS
+        if create_table.if_not_exists && self.table_mut(&table_name).is_some() {
+            return;
+        }
+        let columns: Vec<ColumnInternal> = create_table
+            .columns
+            .iter()
+            .map(|col| self.column_def(&table_name, col))
+            .collect();
+        self.tables.retain(|(name, _)| name != &table_name);
+        self.tables.push((table_name.clone(), columns));
+        for constraint in &create_table.constraints {
+            self.table_constraint(&table_name, constraint);
+        }
+    }
+
+    fn alter_table(&mut self, name: &ObjectName, operations: &[AlterTableOperation]) {
+        let Some(table_name) = self.object_name(name) else {
+            return;
+        };
+        for operation in operations {
+            match operation {
+                AlterTableOperation::AddConstraint(constraint) => {
+                    self.table_constraint(&table_name, constraint)
+                }
+                AlterTableOperation::AddColumn { column_def, .. } => {
+                    let column = self.column_def(&table_name, column_def);
+                    if let Some(columns) = self.table_mut(&table_name) {
+                        columns.push(column);
+                    }
+                }
+                AlterTableOperation::DropColumn { column_name, .. } => {
+                    let column_name = ident_name(column_name);
+                    if let Some(columns) = self.table_mut(&table_name) {
+                        columns.retain(|col| col.name != column_name);
+                    }
+                    self.fks.retain(|fk| {
+                        !(fk.source_table_name == table_name
+                            && fk.source_columns.contains(&column_name))
+                    });
+                    self.indexes.retain(|(tbl, idx)| {
+                        !(tbl == &table_name && idx.columns.contains(&column_name))
+                    });
+                }
+                AlterTableOperation::AlterColumn { column_name, op } => {
+                    let column_names = [ident_name(column_name)];
+                    let is_default =
+                        |c: &ColumnConstraints| matches!(c, ColumnConstraints::Default(_));
+                    match op {
+                        AlterColumnOperation::SetNotNull => self.add_column_constraint(
+                            &table_name,
+                            &column_names,
+                            ColumnConstraints::NotNull,
+                        ),
+                        AlterColumnOperation::DropNotNull => {
+                            self.remove_column_constraint(&table_name, &column_names[0], |c| {
+                                c == &ColumnConstraints::NotNull
+                            })
+                        }
+                        AlterColumnOperation::SetDefault { value } => {
+                            self.remove_column_constraint(
+                                &table_name,
+                                &column_names[0],
+                                is_default,
+                            );
+                            self.add_column_constraint(
+                                &table_name,
+                                &column_names,
+                                ColumnConstraints::Default(self.expression(value)),
+                            )
+                        }
+                        AlterColumnOperation::DropDefault => {
+                            self.remove_column_constraint(&table_name, &column_names[0], is_default)
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_index(&mut self, table_name: &QualifiedName, index: TableIndex) {
+        // Index names are unique within schema
+        self.indexes
+            .retain(|(tbl, idx)| !(tbl.0 == table_name.0 && idx.name == index.name));
+        self.indexes.push((table_name.clone(), index));
+    }
+
+    /// Index that PostgreSQL creates for PRIMARY KEY and UNIQUE constraints
+    fn constraint_index(
+        &mut self,
+        table_name: &QualifiedName,
+        name: Option<&Ident>,
+        columns: Vec<String>,
+        primary: bool,
+    ) {
+        let name = match name {
+            Some(name) => ident_name(name),
+            None if primary => format!("{}_pkey", table_name.1),
+            None => format!("{}_{}_key", table_name.1, columns.join("_")),
+        };
+        self.add_index(
+            table_name,
+            TableIndex {
+                name,
+                columns,
+                unique: true,
+                method: "btree".into(),
+                predicate: None,
+                include: vec![],
+            },
+        );
+    }
+
+    fn create_index(&mut self, create_index: &CreateIndex) {
+        let Some(table_name) = self.object_name(&create_index.table_name) else {
+            return;
+        };
+        // Expression indexes don't mark columns
+        let column_names: Vec<String> = create_index
+            .columns
+            .iter()
+            .filter_map(|c| match &c.expr {
+                Expr::Identifier(ident) => Some(ident_name(ident)),
+                _ => None,
+            })
+            .collect();
+        if !create_index.unique {
+            self.add_column_constraint(&table_name, &column_names, ColumnConstraints::Index);
+        } else if create_index.columns.len() == 1 {
+            self.add_column_constraint(&table_name, &column_names, ColumnConstraints::Unique);
+        }
+
+        let columns: Vec<String> = create_index
+            .columns
+            .iter()
+            .map(|c| match &c.expr {
+                Expr::Identifier(ident) => ident_name(ident),
+                expr => self.expression(expr),
+            })
+            .collect();
+        let name = match &create_index.name {
+            Some(name) => name.0.last().map(ident_name).unwrap_or_default(),
+            // The same name as PostgreSQL generates
+            None if column_names.len() == columns.len() => {
+                format!("{}_{}_idx", table_name.1, column_names.join("_"))
+            }
+            None => format!("{}_expr_idx", table_name.1),
+        };
+        self.add_index(
+            &table_name,
+            TableIndex {
+                name,
+                columns,
+                unique: create_index.unique,
+                method: create_index
+                    .using
+                    .as_ref()
+                    .map_or("btree".into(), |m| m.value.to_lowercase()),
+                predicate: create_index.predicate.as_ref().map(|p| self.expression(p)),
+                include: create_index.include.iter().map(ident_name).collect(),
+            },
+        );
+    }
+
+    /// COMMENT ON TABLE tbl IS '...', COMMENT ON COLUMN tbl.col IS '...'
+    fn comment(&mut self, object_type: &CommentObject, name: &ObjectName, comment: Option<String>) {
+        let key = match object_type {
+            CommentObject::Table => self.object_name(name).map(|tbl| (tbl, None)),
+            CommentObject::Column => name.0.split_last().and_then(|(column, table)| {
+                self.object_name(&ObjectName(table.to_vec()))
+                    .map(|tbl| (tbl, Some(ident_name(column))))
+            }),
+            _ => None,
+        };
+        let Some(key) = key else {
+            return;
+        };
+        // COMMENT ... IS NULL removes comment
+        match comment {
+            Some(comment) => self.comments.insert(key, comment),
+            None => self.comments.remove(&key),
+        };
+    }
+
+    fn create_view(&mut self, name: &ObjectName, materialized: bool, query: &Query) {
+        let Some(view_name) = self.object_name(name) else {
+            return;
+        };
+        let columns = self.view_columns(query);
+        let mut depends_on = BTreeSet::new();
+        let _ = visit_relations(query, |relation| {
+            if let Some(name) = self.object_name(relation) {
+                depends_on.insert(name);
+            }
+            ControlFlow::<()>::Continue(())
+        });
+        depends_on.remove(&view_name);
+        self.views.retain(|v| v.name != view_name);
+        self.views.push(ViewInternal {
+            name: view_name,
+            materialized,
+            columns,
+            depends_on,
+        });
+    }
+
+    /// Columns of entity (table or view) that is used in FROM clause of view
+    fn entity_columns(&self, name: &ObjectName) -> Vec<(String, String)> {
+        let Some(name) = self.object_name(name) else {
+            return vec![];
+        };
+        if let Some((_, columns)) = self.tables.iter().find(|(n, _)| n == &name) {
+            return columns
+                .iter()
+                .map(|c| (c.name.clone(), c.datatype.clone()))
+                .collect();
+        }
+        if let Some(view) = self.views.iter().find(|v| v.name == name) {
+            return view
+                .columns
+                .iter()
+                .map(|c| (c.name.clone(), c.datatype.clone()))
+                .collect();
+        }
+        vec![]
+    }
+
+    fn view_columns(&self, query: &Query) -> Vec<ColumnInternal> {
+        let mut body = query.body.as_ref();
+        // For UNION and others PostgreSQL takes columns from the first query
+        while let SetExpr::SetOperation { left, .. } = body {
+            body = left.as_ref();
+        }
+        let SetExpr::Select(select) = body else {
+            return vec![];
+        };
+
+        // alias (or name) -> columns
+        let mut scope: Vec<(String, Vec<(String, String)>)> = vec![];
+        for table_with_joins in &select.from {
+            let relations = std::iter::once(&table_with_joins.relation)
+                .chain(table_with_joins.joins.iter().map(|j| &j.relation));
+            for relation in relations {
+                if let TableFactor::Table { name, alias, .. } = relation {
+                    let alias = match alias {
+                        Some(alias) => ident_name(&alias.name),
+                        None => name.0.last().map(ident_name).unwrap_or_default(),
+                    };
+                    scope.push((alias, self.entity_columns(name)));
+                }
+            }
+        }
+
+        let mut res = vec![];
+        for item in &select.projection {
+            match item {
+                SelectItem::UnnamedExpr(expr) => res.push(ColumnInternal {
+                    name: expr_column_name(expr),
+                    datatype: infer_datatype(expr, &scope),
+                    constraints: BTreeSet::new(),
+                }),
+                SelectItem::ExprWithAlias { expr, alias } => res.push(ColumnInternal {
+                    name: ident_name(alias),
+                    datatype: infer_datatype(expr, &scope),
+                    constraints: BTreeSet::new(),
+                }),
+                SelectItem::Wildcard(_) => {
+                    for (_, columns) in &scope {
+                        res.extend(columns.iter().map(|(name, datatype)| ColumnInternal {
+                            name: name.clone(),
+                            datatype: datatype.clone(),
+                            constraints: BTreeSet::new(),
+                        }));
+                    }
+                }
+                SelectItem::QualifiedWildcard(name, _) => {
+                    let alias = name.0.last().map(ident_name).unwrap_or_default();
+                    if let Some((_, columns)) = scope.iter().find(|(a, _)| a == &alias) {
+                        res.extend(columns.iter().map(|(name, datatype)| ColumnInternal {
+                            name: name.clone(),
+                            datatype: datatype.clone(),
+                            constraints: BTreeSet::new(),
+                        }));
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    fn apply(&mut self, statement: &Statement) {
+        match statement {
+            Statement::CreateTable(create_table) => self.create_table(create_table),
+            Statement::CreateIndex(create_index) => self.create_index(create_index),
+            Statement::AlterTable {
+                name, operations, ..
+            } => self.alter_table(name, operations),
+            Statement::CreateType {
+                name,
+                representation: UserDefinedTypeRepresentation::Enum { labels },
+            } => {
+                if let Some(name) = self.object_name(name) {
+                    self.enums
+                        .insert(name, labels.iter().map(|l| l.value.clone()).collect());
+                }
+            }
+            Statement::CreateView {
+                name,
+                materialized,
+                query,
+                ..
+            } => self.create_view(name, *materialized, query),
+            Statement::Comment {
+                object_type,
+                object_name,
+                comment,
+                ..
+            } => self.comment(object_type, object_name, comment.clone()),
+            Statement::Drop {
+                object_type, names, ..
+            } => {
+                let names: Vec<QualifiedName> =
+                    names.iter().filter_map(|n| self.object_name(n)).collect();
+                for name in names {
+                    match object_type {
+                        ObjectType::Table => {
+                            self.tables.retain(|(n, _)| n != &name);
+                            // FKs which reference dropped table are dropped by CASCADE
+                            self.fks.retain(|fk| {
+                                fk.source_table_name != name && fk.target_table_name != name
+                            });
+                            self.indexes.retain(|(tbl, _)| tbl != &name);
+                            self.comments.retain(|(tbl, _), _| tbl != &name);
+                        }
+                        ObjectType::Index => self
+                            .indexes
+                            .retain(|(tbl, idx)| !(tbl.0 == name.0 && idx.name == name.1)),
+                        ObjectType::View => self.views.retain(|v| v.name != name),
+                        ObjectType::Type => {
+                            self.enums.remove(&name);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Name is qualified as `schema.table` if several schemas are loaded
+    fn entity_name(&self, (schema_name, name): &QualifiedName) -> String {
+        if self.qualify_names {
+            format!("{schema_name}.{name}")
+        } else {
+            name.clone()
+        }
+    }
+
+    // Ex: public.status -> status if names aren't qualified
+    fn datatype(&self, datatype: &str) -> String {
+        self.enums
+            .keys()
+            .find(|(schema_name, name)| datatype == format!("{schema_name}.{name}"))
+            .map_or(datatype.to_string(), |name| self.entity_name(name))
+    }
+
+    fn get_fks(&self, tbls: &[Arc<Table>]) -> Result<Vec<ForeignKey>, SqlantError> {
+        let find_table = |name: &str| tbls.iter().find(|tbl| tbl.name == name);
+        let find_columns = |tbl: &Table, names: &[String]| {
+            names
+                .iter()
+                .map(|name| {
+                    tbl.columns
+                        .iter()
+                        .find(|col| &col.name == name)
+                        .map(Arc::clone)
+                })
+                .collect::<Option<Vec<_>>>()
+        };
+
+        let mut res = vec![];
+        for fk in &self.fks {
+            let (source_name, target_name) = (
+                self.entity_name(&fk.source_table_name),
+                self.entity_name(&fk.target_table_name),
+            );
+            let fk_data = || {
+                let source_table = find_table(&source_name)?;
+                let target_table = find_table(&target_name)?;
+                let target_columns = if fk.target_columns.is_empty() {
+                    // REFERENCES target -- references PK
+                    target_table
+                        .columns
+                        .iter()
+                        .filter(|col| col.is_pk())
+                        .map(Arc::clone)
+                        .collect()
+                } else {
+                    find_columns(target_table, &fk.target_columns)?
+                };
+                Some(
+                    ForeignKey::new(
+                        Arc::clone(source_table),
+                        find_columns(source_table, &fk.source_columns)?,
+                        Arc::clone(target_table),
+                        target_columns,
+                    )
+                    .with_name(Some(fk.name.clone()))
+                    .with_actions(fk.on_delete, fk.on_update)
+                    .with_deferrable(fk.deferrable.0, fk.deferrable.1),
+                )
+            };
+            res.push(fk_data().ok_or_else(|| {
+                SqlantError::dangling_foreign_key(
+                    &source_name,
+                    &fk.source_columns,
+                    &target_name,
+                    &fk.target_columns,
+                )
+            })?);
+        }
+        Ok(res)
+    }
+
+    /// Applies all statements to the empty state of loader
+    fn build(mut self) -> Result<SqlERData, SqlantError> {
+        for statement in self.parse_statements()? {
+            self.apply(&statement);
+        }
+
+        let schemas: BTreeSet<&str> = self
+            .tables
+            .iter()
+            .map(|(name, _)| name)
+            .chain(self.views.iter().map(|v| &v.name))
+            .map(|(schema_name, _)| schema_name.as_str())
+            .collect();
+        self.qualify_names =
+            self.schema_names.len() > 1 || (self.is_all_schemas() && schemas.len() > 1);
+
+        let fk_columns: BTreeSet<(&QualifiedName, &str)> = self
+            .fks
+            .iter()
+            .flat_map(|fk| {
+                fk.source_columns
+                    .iter()
+                    .map(|c| (&fk.source_table_name, c.as_str()))
+            })
+            .collect();
+
+        let mut tables: Vec<Arc<Table>> = self
+            .tables
+            .iter()
+            .map(|(table_name, columns)| {
+                let mut columns: Vec<Arc<TableColumn>> = columns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, col)| {
+                        let mut constraints = col.constraints.clone();
+                        if fk_columns.contains(&(table_name, col.name.as_str())) {
+                            constraints.insert(ColumnConstraints::ForeignKey);
+                        }
+                        Arc::new(TableColumn {
+                            name: col.name.clone(),
+                            // Keep it 1-based like attnum in PostgreSQL
+                            col_num: i as i16 + 1,
+                            datatype: self.datatype(&col.datatype),
+                            constraints,
+                            comment: self
+                                .comments
+                                .get(&(table_name.clone(), Some(col.name.clone())))
+                                .cloned(),
+                        })
+                    })
+                    .collect();
+                // The same order as PostgreSqlERDLoader has
+                columns.sort_by(|a, b| a.name.cmp(&b.name));
+                let mut tbl = Table::new(self.entity_name(table_name), columns);
+                tbl.schema = Some(table_name.0.clone());
+                tbl.comment = self.comments.get(&(table_name.clone(), None)).cloned();
+                tbl.indexes = self
+                    .indexes
+                    .iter()
+                    .filter(|(tbl, _)| tbl == table_name)
+                    .map(|(_, idx)| idx.clone())
+                    .collect();
+                // The same order as PostgreSqlERDLoader has
+                tbl.indexes.sort_by(|a, b| a.name.cmp(&b.name));
+                Arc::new(tbl)
+            })
+            .collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut foreign_keys = self.get_fks(&tables)?;
+        foreign_keys.sort_by(|a, b| a.source_table.name.cmp(&b.source_table.name));
+
+        let mut views: Vec<Arc<View>> = self
+            .views
+            .iter()
+            .map(|v| {
+                let mut columns: Vec<Arc<TableColumn>> = v
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, col)| {
+                        Arc::new(TableColumn {
+                            name: col.name.clone(),
+                            col_num: i as i16 + 1,
+                            datatype: self.datatype(&col.datatype),
+                            constraints: col.constraints.clone(),
+                            comment: self
+                                .comments
+                                .get(&(v.name.clone(), Some(col.name.clone())))
+                                .cloned(),
+                        })
+                    })
+                    .collect();
+                columns.sort_by(|a, b| a.name.cmp(&b.name));
+                Arc::new(View {
+                    materialized: v.materialized,
+                    name: self.entity_name(&v.name),
+                    schema: Some(v.name.0.clone()),
+                    columns,
+                    comment: self.comments.get(&(v.name.clone(), None)).cloned(),
+                    // Names of CTEs are relations too, so only created entities are kept
+                    depends_on: v
+                        .depends_on
+                        .iter()
+                        .filter(|name| {
+                            self.tables.iter().any(|(tbl, _)| tbl == *name)
+                                || self.views.iter().any(|view| &view.name == *name)
+                        })
+                        .map(|name| self.entity_name(name))
+                        .collect(),
+                    // Columns can't be resolved without database
+                    used_columns: BTreeMap::new(),
+                })
+            })
+            .collect();
+        // Materialized views go first like in PostgreSqlERDLoader
+        views.sort_by(|a, b| (!a.materialized, &a.name).cmp(&(!b.materialized, &b.name)));
+
+        Ok(SqlERData {
+            tables,
+            foreign_keys,
+            enums: self
+                .enums
+                .iter()
+                .map(|(name, values)| (self.entity_name(name), values.clone()))
+                .collect(),
+            views,
+        })
+    }
+}
+
+/// Ex: CREATE UNLOGGED TABLE -> Some("CREATE TABLE"), CREATE FUNCTION -> None
+fn table_statement_kind(tokens: &[Token]) -> Option<&'static str> {
+    let keywords: Vec<Keyword> = tokens
+        .iter()
+        .map_while(|token| match token {
+            Token::Word(word) => Some(word.keyword),
+            _ => None,
+        })
+        .collect();
+    match keywords.as_slice() {
+        [Keyword::ALTER, Keyword::TABLE, ..] => Some("ALTER TABLE"),
+        [Keyword::CREATE, rest @ ..] => rest
+            .iter()
+            .find(|keyword| {
+                !matches!(
+                    keyword,
+                    Keyword::OR
+                        | Keyword::REPLACE
+                        | Keyword::GLOBAL
+                        | Keyword::LOCAL
+                        | Keyword::TEMP
+                        | Keyword::TEMPORARY
+                        | Keyword::UNLOGGED
+                )
+            })
+            .filter(|keyword| **keyword == Keyword::TABLE)
+            .map(|_| "CREATE TABLE"),
+        _ => None,
+    }
+}
+
+/// Unquoted identifiers are folded to lower case the same way as PostgreSQL does it
+fn ident_name(ident: &Ident) -> String {
+    match ident.quote_style {
+        Some(_) => ident.value.clone(),
+        None => ident.value.to_lowercase(),
+    }
+}
+
+/// Name of FK constraint, unnamed one is named the same way as PostgreSQL does it
+fn fk_name(table_name: &QualifiedName, name: Option<&Ident>, source_columns: &[String]) -> String {
+    match name {
+        Some(name) => ident_name(name),
+        None => format!("{}_{}_fkey", table_name.1, source_columns.join("_")),
+    }
+}
+
+fn referential_action(action: Option<&sqlparser::ast::ReferentialAction>) -> ReferentialAction {
+    action
+        .map(|action| ReferentialAction::from_sql(&action.to_string()))
+        .unwrap_or_default()
+}
+
+/// INITIALLY DEFERRED implies DEFERRABLE
+fn deferrable(characteristics: Option<&ConstraintCharacteristics>) -> (bool, bool) {
+    let Some(characteristics) = characteristics else {
+        return (false, false);
+    };
+    let initially_deferred = characteristics.initially == Some(DeferrableInitial::Deferred);
+    (
+        characteristics.deferrable == Some(true) || initially_deferred,
+        initially_deferred,
+    )
+}
+
+/// Name of view column without alias, the same way as PostgreSQL does it
+fn expr_column_name(expr: &Expr) -> String {
+    match expr {
+        Expr::Identifier(ident) => ident_name(ident),
+        Expr::CompoundIdentifier(idents) => idents.last().map(ident_name).unwrap_or_default(),
+        Expr::Function(func) => func
+            .name
+            .0
+            .last()
+            .map(|i| i.value.to_lowercase())
+            .unwrap_or_default(),
+        Expr::Cast { expr, .. } | Expr::Nested(expr) => expr_column_name(expr),
+        _ => "?column?".to_string(),
+    }
+}
+
+/// Best effort datatype inference of view column
+fn infer_datatype(expr: &Expr, scope: &[(String, Vec<(String, String)>)]) -> String {
+    let find_column = |table: Option<&str>, column: &str| {
+        scope
+            .iter()
+            .filter(|(alias, _)| table.is_none_or(|t| t == alias))
+            .flat_map(|(_, columns)| columns)
+            .find(|(name, _)| name == column)
+            .map(|(_, datatype)| datatype.clone())
+    };
+    let unknown = || "unknown".to_string();
+
+    match expr {
+        Expr::Identifier(ident) => find_column(None, &ident_name(ident)).unwrap_or_else(unknown),
+        Expr::CompoundIdentifier(idents) => match idents.as_slice() {
+            [.., table, column] => {
+                find_column(Some(&ident_name(table)), &ident_name(column)).unwrap_or_else(unknown)
+            }
+            [column] => find_column(None, &ident_name(column)).unwrap_or_else(unknown),
+            [] => unknown(),
+        },
+        Expr::Cast { data_type, .. } => normalize_datatype(data_type),
+        Expr::Nested(expr) => infer_datatype(expr, scope),
+        Expr::Function(func) => {
+            let name = func
+                .name
+                .0
+                .last()
+                .map(|i| i.value.to_lowercase())
+                .unwrap_or_default();
+            let first_arg_type = || match &func.args {
+                FunctionArguments::List(list) => list
+                    .args
+                    .iter()
+                    .find_map(|arg| match arg {
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => {
+                            Some(infer_datatype(expr, scope))
+                        }
+                        _ => None,
+                    })
+                    .unwrap_or_else(unknown),
+                _ => unknown(),
+            };
+            match name.as_str() {
+                "count" => "bigint".to_string(),
+                // https://www.postgresql.org/docs/current/functions-aggregate.html
+                "sum" => match first_arg_type().as_str() {
+                    "smallint" | "integer" => "bigint".to_string(),
+                    "real" => "real".to_string(),
+                    "double precision" => "double precision".to_string(),
+                    _ => "numeric".to_string(),
+                },
+                "avg" => match first_arg_type().as_str() {
+                    "real" | "double precision" => "double precision".to_string(),
+                    _ => "numeric".to_string(),
+                },
+                "min" | "max" | "coalesce" => first_arg_type(),
+                "now" => "timestamp with time zone".to_string(),
+                _ => unknown(),
+            }
+        }
+        _ => unknown(),
+    }
+}
+
+/// Converts datatype to the name that PostgreSQL shows in catalog (`regtype`)
+fn normalize_datatype(data_type: &DataType) -> String {
+    let timezone = |tz: &TimezoneInfo| match tz {
+        TimezoneInfo::WithTimeZone | TimezoneInfo::Tz => "with time zone",
+        _ => "without time zone",
+    };
+    match data_type {
+        DataType::SmallInt(_) | DataType::Int2(_) => "smallint".into(),
+        DataType::Int(_) | DataType::Integer(_) | DataType::Int4(_) => "integer".into(),
+        DataType::BigInt(_) | DataType::Int8(_) => "bigint".into(),
+        DataType::Real | DataType::Float4 => "real".into(),
+        DataType::DoublePrecision | DataType::Double | DataType::Float8 => {
+            "double precision".into()
+        }
+        DataType::Numeric(_) | DataType::Decimal(_) | DataType::Dec(_) => "numeric".into(),
+        DataType::Bool | DataType::Boolean => "boolean".into(),
+        DataType::Varchar(_) | DataType::CharacterVarying(_) | DataType::CharVarying(_) => {
+            "character varying".into()
+        }
+        DataType::Char(_) | DataType::Character(_) => "character".into(),
+        DataType::Timestamp(_, tz) => format!("timestamp {}", timezone(tz)),
+        DataType::Time(_, tz) => format!("time {}", timezone(tz)),
+        DataType::Array(ArrayElemTypeDef::SquareBracket(inner, _))
+        | DataType::Array(ArrayElemTypeDef::AngleBracket(inner))
+        | DataType::Array(ArrayElemTypeDef::Parenthesis(inner)) => {
+            format!("{}[]", normalize_datatype(inner))
+        }
+        DataType::Custom(name, _) => {
+            let name = name.0.last().map(ident_name).unwrap_or_default();
+            match name.to_lowercase().as_str() {
+                "serial" | "serial4" => "integer".into(),
+                "bigserial" | "serial8" => "bigint".into(),
+                "smallserial" | "serial2" => "smallint".into(),
+                "timestamptz" => "timestamp with time zone".into(),
+                "timetz" => "time with time zone".into(),
+                "bool" => "boolean".into(),
+                _ => name,
+            }
+        }
+        other => other.to_string().to_lowercase(),
+    }
+}
+
+fn is_serial(data_type: &DataType) -> bool {
+    let DataType::Custom(name, _) = data_type else {
+        return false;
+    };
+    name.0.last().is_some_and(|i| {
+        matches!(
+            i.value.to_lowercase().as_str(),
+            "serial" | "serial4" | "bigserial" | "serial8" | "smallserial" | "serial2"
+        )
+    })
+}
+
+#[async_trait::async_trait]
+impl SqlERDataLoader for DdlERDLoader {
+    async fn load_erd_data(&mut self) -> Result<SqlERData, crate::SqlantError> {
+        // Loader can be called several times, so statements aren't applied to self
+        Self::new(self.sql.clone(), self.schema_names.clone()).build()
+    }
+}
@@ -0,0 +1,586 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use crate::sql_entities::{
+    ColumnConstraints, ForeignKey, SqlERData, Table, TableColumn, TableIndex, View,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl Change {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Change::Added => "added",
+            Change::Removed => "removed",
+            Change::Changed => "changed",
+        }
+    }
+}
+
+// Compares Option of old and new object. Ex: compare(None, Some(&tbl)) == Some(Added)
+fn compare<T, F: Fn(&T, &T) -> bool>(old: Option<&T>, new: Option<&T>, eq: F) -> Option<Change> {
+    match (old, new) {
+        (None, Some(_)) => Some(Change::Added),
+        (Some(_), None) => Some(Change::Removed),
+        (Some(old), Some(new)) if !eq(old, new) => Some(Change::Changed),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnDiff {
+    pub change: Change,
+    // Set if column is changed
+    pub old_datatype: Option<String>,
+    pub new_datatype: Option<String>,
+    pub added_constraints: Vec<ColumnConstraints>,
+    pub removed_constraints: Vec<ColumnConstraints>,
+}
+
+impl ColumnDiff {
+    fn new(old: Option<&TableColumn>, new: Option<&TableColumn>) -> Option<ColumnDiff> {
+        let change = compare(old, new, |old, new| {
+            old.datatype == new.datatype
+                && constraints(old) == constraints(new)
+                && old.comment == new.comment
+        })?;
+        let mut diff = ColumnDiff {
+            change,
+            old_datatype: None,
+            new_datatype: None,
+            added_constraints: vec![],
+            removed_constraints: vec![],
+        };
+        if let (Some(old), Some(new)) = (old, new) {
+            if old.datatype != new.datatype {
+                diff.old_datatype = Some(old.datatype.clone());
+                diff.new_datatype = Some(new.datatype.clone());
+            }
+            let difference = |a: &TableColumn, b: &TableColumn| {
+                let b_constraints = constraints(b);
+                a.constraints
+                    .iter()
+                    .filter(|c| !b_constraints.contains(&constraint(c)))
+                    .cloned()
+                    .collect()
+            };
+            diff.added_constraints = difference(new, old);
+            diff.removed_constraints = difference(old, new);
+        }
+        Some(diff)
+    }
+}
+
+// Type names which consist of several words. Ex: 'a'::character varying
+static MULTI_WORD_TYPE_SUFFIXES: [&str; 4] = [
+    " varying",
+    " precision",
+    " with time zone",
+    " without time zone",
+];
+
+// PostgreSQL adds casts to expressions that it stores, so they are removed before comparison.
+// Ex: payment_method = 'card'::text -> payment_method = 'card'
+fn strip_casts(expression: &str) -> String {
+    let mut res = String::new();
+    let mut rest = expression;
+    let mut in_string = false;
+    while let Some(ch) = rest.chars().next() {
+        if !in_string && rest.starts_with("::") {
+            rest = rest[2..].trim_start_matches(|c: char| {
+                c.is_alphanumeric() || matches!(c, '_' | '.' | '"' | '[' | ']')
+            });
+            while let Some(suffix) = MULTI_WORD_TYPE_SUFFIXES
+                .iter()
+                .find(|suffix| rest.starts_with(*suffix))
+            {
+                rest = &rest[suffix.len()..];
+            }
+            continue;
+        }
+        if ch == '\'' {
+            in_string = !in_string;
+        }
+        res.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+    res
+}
+
+// Expressions of constraints without casts
+fn constraint(constraint: &ColumnConstraints) -> ColumnConstraints {
+    match constraint {
+        ColumnConstraints::Check(expression) => ColumnConstraints::Check(strip_casts(expression)),
+        ColumnConstraints::Default(expression) => {
+            ColumnConstraints::Default(strip_casts(expression))
+        }
+        constraint => constraint.clone(),
+    }
+}
+
+fn constraints(col: &TableColumn) -> BTreeSet<ColumnConstraints> {
+    col.constraints.iter().map(constraint).collect()
+}
+
+fn index_eq(old: &TableIndex, new: &TableIndex) -> bool {
+    let expressions = |idx: &TableIndex| {
+        (
+            idx.columns
+                .iter()
+                .map(|col| strip_casts(col))
+                .collect::<Vec<_>>(),
+            idx.predicate.as_deref().map(strip_casts),
+        )
+    };
+    old.unique == new.unique
+        && old.method == new.method
+        && old.include == new.include
+        && expressions(old) == expressions(new)
+}
+
+// Columns used by query are loaded from PostgreSQL only, so they aren't compared
+fn view_eq(old: &View, new: &View) -> bool {
+    old.materialized == new.materialized
+        && old.columns == new.columns
+        && old.comment == new.comment
+        && old.depends_on == new.depends_on
+}
+
+fn find_column<'a>(tbl: &'a Table, name: &str) -> Option<&'a TableColumn> {
+    tbl.columns
+        .iter()
+        .find(|col| col.name == name)
+        .map(|col| col.as_ref())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableDiff {
+    pub change: Change,
+    // Only changed columns and indexes of changed table
+    pub columns: BTreeMap<String, ColumnDiff>,
+    pub indexes: BTreeMap<String, Change>,
+    pub comment_changed: bool,
+}
+
+impl TableDiff {
+    fn new(old: Option<&Table>, new: Option<&Table>) -> Option<TableDiff> {
+        let (Some(old), Some(new)) = (old, new) else {
+            return Some(TableDiff {
+                change: compare(old, new, |_, _| true)?,
+                columns: BTreeMap::new(),
+                indexes: BTreeMap::new(),
+                comment_changed: false,
+            });
+        };
+
+        let column_names: BTreeSet<&str> = old
+            .columns
+            .iter()
+            .chain(new.columns.iter())
+            .map(|col| col.name.as_str())
+            .collect();
+        let columns: BTreeMap<String, ColumnDiff> = column_names
+            .into_iter()
+            .filter_map(|name| {
+                ColumnDiff::new(find_column(old, name), find_column(new, name))
+                    .map(|diff| (name.to_string(), diff))
+            })
+            .collect();
+
+        let index_names: BTreeSet<&str> = old
+            .indexes
+            .iter()
+            .chain(new.indexes.iter())
+            .map(|idx| idx.name.as_str())
+            .collect();
+        let indexes: BTreeMap<String, Change> = index_names
+            .into_iter()
+            .filter_map(|name| {
+                compare(
+                    old.indexes.iter().find(|idx| idx.name == name),
+                    new.indexes.iter().find(|idx| idx.name == name),
+                    index_eq,
+                )
+                .map(|change| (name.to_string(), change))
+            })
+            .collect();
+
+        let comment_changed = old.comment != new.comment;
+        if columns.is_empty() && indexes.is_empty() && !comment_changed {
+            return None;
+        }
+        Some(TableDiff {
+            change: Change::Changed,
+            columns,
+            indexes,
+            comment_changed,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumDiff {
+    pub change: Change,
+    pub added_values: Vec<String>,
+    pub removed_values: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForeignKeyDiff {
+    pub change: Change,
+    // Set if FK is changed: (attribute, old value, new value). Ex: ("ON DELETE", "CASCADE", "RESTRICT")
+    pub changed_attributes: Vec<(&'static str, String, String)>,
+}
+
+impl ForeignKeyDiff {
+    fn new(old: Option<&ForeignKey>, new: Option<&ForeignKey>) -> Option<ForeignKeyDiff> {
+        let changed_attributes = match (old, new) {
+            (Some(old), Some(new)) => foreign_key_attributes(old)
+                .into_iter()
+                .zip(foreign_key_attributes(new))
+                .filter(|((_, old_value), (_, new_value))| old_value != new_value)
+                .filter_map(|((attribute, old_value), (_, new_value))| {
+                    Some((attribute, old_value?, new_value?))
+                })
+                .collect(),
+            _ => vec![],
+        };
+        Some(ForeignKeyDiff {
+            change: compare(old, new, |_, _| changed_attributes.is_empty())?,
+            changed_attributes,
+        })
+    }
+}
+
+// Attributes of FK that are compared by diff.
+// Value is None if it's unknown (SQLite doesn't name foreign keys).
+fn foreign_key_attributes(fk: &ForeignKey) -> [(&'static str, Option<String>); 5] {
+    let deferrable = match (fk.deferrable, fk.initially_deferred) {
+        (false, _) => "NOT DEFERRABLE",
+        (true, false) => "DEFERRABLE INITIALLY IMMEDIATE",
+        (true, true) => "DEFERRABLE INITIALLY DEFERRED",
+    };
+    [
+        ("name", fk.name.clone()),
+        ("ON DELETE", Some(fk.on_delete.to_string())),
+        ("ON UPDATE", Some(fk.on_update.to_string())),
+        ("deferrable", Some(deferrable.to_string())),
+        ("MATCH", Some(fk.match_type.to_string())),
+    ]
+}
+
+// Differences between two models (old and new).
+// Objects are identified by names, FKs by names of tables and columns.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SqlERDiff {
+    pub tables: BTreeMap<String, TableDiff>,
+    pub foreign_keys: BTreeMap<String, ForeignKeyDiff>,
+    pub enums: BTreeMap<String, EnumDiff>,
+    pub views: BTreeMap<String, Change>,
+}
+
+// Ex: order_detail(sku_id) -> sku(id)
+// Pairs of columns are sorted, so order of columns in FK doesn't matter.
+pub fn foreign_key_name(fk: &ForeignKey) -> String {
+    let mut pairs: Vec<(&str, &str)> = fk
+        .source_columns
+        .iter()
+        .zip(fk.target_columns.iter())
+        .map(|(source, target)| (source.name.as_str(), target.name.as_str()))
+        .collect();
+    pairs.sort();
+    let (source_names, target_names): (Vec<&str>, Vec<&str>) = pairs.into_iter().unzip();
+    format!(
+        "{}({}) -> {}({})",
+        fk.source_table.name,
+        source_names.join(", "),
+        fk.target_table.name,
+        target_names.join(", ")
+    )
+}
+
+impl SqlERDiff {
+    pub fn new(old: &SqlERData, new: &SqlERData) -> SqlERDiff {
+        let table_names: BTreeSet<&str> = old
+            .tables
+            .iter()
+            .chain(new.tables.iter())
+            .map(|tbl| tbl.name.as_str())
+            .collect();
+        let find_table =
+            |erd: &SqlERData, name: &str| erd.tables.iter().find(|tbl| tbl.name == name).cloned();
+        let tables = table_names
+            .into_iter()
+            .filter_map(|name| {
+                TableDiff::new(
+                    find_table(old, name).as_deref(),
+                    find_table(new, name).as_deref(),
+                )
+                .map(|diff| (name.to_string(), diff))
+            })
+            .collect();
+
+        let old_fks: BTreeMap<String, &ForeignKey> = old
+            .foreign_keys
+            .iter()
+            .map(|fk| (foreign_key_name(fk), fk))
+            .collect();
+        let new_fks: BTreeMap<String, &ForeignKey> = new
+            .foreign_keys
+            .iter()
+            .map(|fk| (foreign_key_name(fk), fk))
+            .collect();
+        let fk_names: BTreeSet<&String> = old_fks.keys().chain(new_fks.keys()).collect();
+        let foreign_keys = fk_names
+            .into_iter()
+            .filter_map(|name| {
+                ForeignKeyDiff::new(old_fks.get(name).copied(), new_fks.get(name).copied())
+                    .map(|diff| (name.clone(), diff))
+            })
+            .collect();
+
+        let enum_names: BTreeSet<&String> = old.enums.keys().chain(new.enums.keys()).collect();
+        let enums = enum_names
+            .into_iter()
+            .filter_map(|name| {
+                let (old_values, new_values) = (old.enums.get(name), new.enums.get(name));
+                let change = compare(old_values, new_values, |old, new| old == new)?;
+                let values_diff = |a: Option<&Vec<String>>, b: Option<&Vec<String>>| match (a, b) {
+                    (Some(a), Some(b)) => a.iter().filter(|v| !b.contains(v)).cloned().collect(),
+                    _ => vec![],
+                };
+                Some((
+                    name.clone(),
+                    EnumDiff {
+                        change,
+                        added_values: values_diff(new_values, old_values),
+                        removed_values: values_diff(old_values, new_values),
+                    },
+                ))
+            })
+            .collect();
+
+        let view_names: BTreeSet<&str> = old
+            .views
+            .iter()
+            .chain(new.views.iter())
+            .map(|view| view.name.as_str())
+            .collect();
+        let views = view_names
+            .into_iter()
+            .filter_map(|name| {
+                compare(
+                    old.views.iter().find(|view| view.name == name),
+                    new.views.iter().find(|view| view.name == name),
+                    |old, new| view_eq(old, new),
+                )
+                .map(|change| (name.to_string(), change))
+            })
+            .collect();
+
+        SqlERDiff {
+            tables,
+            foreign_keys,
+            enums,
+            views,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+            && self.foreign_keys.is_empty()
+            && self.enums.is_empty()
+            && self.views.is_empty()
+    }
+
+    pub fn table_change(&self, table_name: &str) -> Option<Change> {
+        self.tables.get(table_name).map(|diff| diff.change)
+    }
+
+    pub fn column_change(&self, table_name: &str, column_name: &str) -> Option<Change> {
+        self.tables
+            .get(table_name)?
+            .columns
+            .get(column_name)
+            .map(|diff| diff.change)
+    }
+
+    pub fn foreign_key_change(&self, fk: &ForeignKey) -> Option<Change> {
+        self.foreign_keys
+            .get(&foreign_key_name(fk))
+            .map(|diff| diff.change)
+    }
+
+    pub fn enum_change(&self, enum_name: &str) -> Option<Change> {
+        self.enums.get(enum_name).map(|diff| diff.change)
+    }
+
+    pub fn view_change(&self, view_name: &str) -> Option<Change> {
+        self.views.get(view_name).copied()
+    }
+
+    // New model with removed objects (tables, columns, FKs, enums, views) of old one.
+    // It's used to draw diff diagram.
+    pub fn merge(&self, old: &SqlERData, new: &SqlERData) -> SqlERData {
+        let mut tables: Vec<Arc<Table>> = new
+            .tables
+            .iter()
+            .map(|tbl| {
+                let removed_columns: Vec<Arc<TableColumn>> = match self.tables.get(&tbl.name) {
+                    Some(diff) => old
+                        .tables
+                        .iter()
+                        .filter(|old_tbl| old_tbl.name == tbl.name)
+                        .flat_map(|old_tbl| old_tbl.columns.iter())
+                        .filter(|col| {
+                            diff.columns
+                                .get(&col.name)
+                                .is_some_and(|c| c.change == Change::Removed)
+                        })
+                        .cloned()
+                        .collect(),
+                    None => vec![],
+                };
+                if removed_columns.is_empty() {
+                    return Arc::clone(tbl);
+                }
+                let mut tbl = tbl.as_ref().clone();
+                tbl.columns.extend(removed_columns);
+                Arc::new(tbl)
+            })
+            .collect();
+        tables.extend(
+            old.tables
+                .iter()
+                .filter(|tbl| self.table_change(&tbl.name) == Some(Change::Removed))
+                .cloned(),
+        );
+
+        let mut foreign_keys = new.foreign_keys.clone();
+        foreign_keys.extend(
+            old.foreign_keys
+                .iter()
+                .filter(|fk| self.foreign_key_change(fk) == Some(Change::Removed))
+                .cloned(),
+        );
+
+        let mut enums = new.enums.clone();
+        for (name, values) in &old.enums {
+            if self.enum_change(name) == Some(Change::Removed) {
+                enums.insert(name.clone(), values.clone());
+            }
+        }
+
+        let mut views = new.views.clone();
+        views.extend(
+            old.views
+                .iter()
+                .filter(|view| self.view_change(&view.name) == Some(Change::Removed))
+                .cloned(),
+        );
+
+        SqlERData {
+            tables,
+            foreign_keys,
+            enums,
+            views,
+        }
+    }
+
+    // Markdown list of changes
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "No changes\n".into();
+        }
+        let mut res = String::new();
+        if !self.tables.is_empty() {
+            res += "## Tables\n";
+            for (name, diff) in &self.tables {
+                res += &format!("- {} `{name}`\n", diff.change.as_str());
+                for (col_name, col) in &diff.columns {
+                    res += &format!("  - {} column `{col_name}`", col.change.as_str());
+                    let mut details = vec![];
+                    if let (Some(old), Some(new)) = (&col.old_datatype, &col.new_datatype) {
+                        details.push(format!("type `{old}` -> `{new}`"));
+                    }
+                    for (prefix, constraints) in [
+                        ("added", &col.added_constraints),
+                        ("removed", &col.removed_constraints),
+                    ] {
+                        details.extend(
+                            constraints
+                                .iter()
+                                .map(|c| format!("{prefix} {}", constraint_name(c))),
+                        );
+                    }
+                    if !details.is_empty() {
+                        res += &format!(": {}", details.join(", "));
+                    }
+                    res += "\n";
+                }
+                for (idx_name, change) in &diff.indexes {
+                    res += &format!("  - {} index `{idx_name}`\n", change.as_str());
+                }
+                if diff.comment_changed {
+                    res += "  - changed comment\n";
+                }
+            }
+        }
+        if !self.foreign_keys.is_empty() {
+            res += "## Foreign keys\n";
+            for (name, diff) in &self.foreign_keys {
+                res += &format!("- {} `{name}`", diff.change.as_str());
+                let details: Vec<String> = diff
+                    .changed_attributes
+                    .iter()
+                    .map(|(attribute, old, new)| format!("{attribute} `{old}` -> `{new}`"))
+                    .collect();
+                if !details.is_empty() {
+                    res += &format!(": {}", details.join(", "));
+                }
+                res += "\n";
+            }
+        }
+        if !self.enums.is_empty() {
+            res += "## Enums\n";
+            for (name, diff) in &self.enums {
+                res += &format!("- {} `{name}`", diff.change.as_str());
+                let mut details = vec![];
+                if !diff.added_values.is_empty() {
+                    details.push(format!("added values {}", diff.added_values.join(", ")));
+                }
+                if !diff.removed_values.is_empty() {
+                    details.push(format!("removed values {}", diff.removed_values.join(", ")));
+                }
+                if !details.is_empty() {
+                    res += &format!(": {}", details.join(", "));
+                }
+                res += "\n";
+            }
+        }
+        if !self.views.is_empty() {
+            res += "## Views\n";
+            for (name, change) in &self.views {
+                res += &format!("- {} `{name}`\n", change.as_str());
+            }
+        }
+        res
+    }
+}
+
+fn constraint_name(constraint: &ColumnConstraints) -> String {
+    match constraint {
+        ColumnConstraints::NotNull => "NOT NULL".into(),
+        ColumnConstraints::PrimaryKey => "PRIMARY KEY".into(),
+        ColumnConstraints::ForeignKey => "FOREIGN KEY".into(),
+        ColumnConstraints::Unique => "UNIQUE".into(),
+        ColumnConstraints::Check(expr) => format!("CHECK `{expr}`"),
+        ColumnConstraints::Default(expr) => format!("DEFAULT `{expr}`"),
+        ColumnConstraints::Index => "INDEX".into(),
+    }
+}
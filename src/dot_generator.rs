@@ -0,0 +1,356 @@
+use std::sync::Arc;
+
+use super::sql_entities::{ReferentialAction, SqlERData, Table, TableColumn, View};
+use crate::plantuml_generator::{PlantUmlDefaultGenerator, CASCADE_COLOR};
+use crate::{Direction, GeneratorConfigOptions, ViewGenerator};
+use serde::Serialize;
+use tinytemplate::{format_unescaped, TinyTemplate};
+
+static DOT_TEMPLATE: &str = r#"digraph sqlant \{
+{{ if direction }}    rankdir={direction}
+{{ endif }}    node [shape=plaintext, fontname="Helvetica"]
+    edge [dir=both, arrowsize=0.8]
+
+{{ for cluster in clusters }}{cluster}{{ endfor }}{{ for ent in entities }}{ent}{{ endfor }}{{ for view in views }}{view}{{ endfor }}{{ for en in enums }}{en}{{ endfor }}
+{{ for fk in foreign_keys }}{fk}{{ endfor }}}
+"#;
+
+static CLUSTER_TEMPLATE: &str = r#"    subgraph "cluster_{name}" \{
+    label="{name}"
+{{ for ent in entities }}{ent}{{ endfor }}{{ for view in views }}{view}{{ endfor }}    }
+
+"#;
+
+static ENTITY_TEMPLATE: &str = "    {id} [{{ if tooltip }}tooltip=\"{tooltip}\", {{ endif }}label=<\n\
+    <TABLE BORDER=\"{{ if is_focused }}2\" COLOR=\"darkorange{{ else }}0{{ endif }}\" CELLBORDER=\"1\" CELLSPACING=\"0\" CELLPADDING=\"4\">\n\
+    <TR><TD COLSPAN=\"3\" BGCOLOR=\"{header_color}\"><B>{title}</B></TD></TR>\n\
+    {{ for col in columns }}{col}{{ endfor }}\
+    {{ for idx in indexes }}<TR><TD COLSPAN=\"3\" ALIGN=\"LEFT\"><I>{idx}</I></TD></TR>\n{{ endfor }}\
+    </TABLE>>]\n";
+
+static COLUMN_TEMPLATE: &str = "<TR><TD ALIGN=\"LEFT\">{markers}</TD><TD ALIGN=\"LEFT\">{name}</TD><TD ALIGN=\"LEFT\">{datatype}</TD></TR>\n";
+
+static ENUM_TEMPLATE: &str = "    {id} [label=<\n\
+    <TABLE BORDER=\"0\" CELLBORDER=\"1\" CELLSPACING=\"0\" CELLPADDING=\"4\">\n\
+    <TR><TD BGCOLOR=\"lightyellow\"><B>{title}</B></TD></TR>\n\
+    {{ for v in values }}<TR><TD ALIGN=\"LEFT\">{v}</TD></TR>\n{{ endfor }}\
+    </TABLE>>]\n";
+
+static REL_TEMPLATE: &str = "    {source_id} -> {target_id} [arrowtail={{ if is_zero_one_to_one }}teeodot{{ else }}crowodot{{ endif }}, arrowhead=teetee{{ if color }}, color=\"{color}\"{{ endif }}]\n";
+
+#[derive(Serialize)]
+struct SDot {
+    direction: Option<&'static str>,
+    // Tables and views grouped by schema if several schemas are loaded
+    clusters: Vec<String>,
+    entities: Vec<String>,
+    views: Vec<String>,
+    enums: Vec<String>,
+    foreign_keys: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct SCluster<'a> {
+    name: &'a str,
+    entities: Vec<String>,
+    views: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct SEntity {
+    id: String,
+    title: String,
+    header_color: &'static str,
+    is_focused: bool,        // Root table of neighbourhood diagram
+    tooltip: Option<String>, // Comments of entity and its columns
+    columns: Vec<String>,
+    indexes: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct SColumn {
+    markers: String, // Ex: PK, FK
+    name: String,
+    datatype: String,
+}
+
+#[derive(Serialize)]
+struct SEnum {
+    id: String,
+    title: String,
+    values: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct SForeignKey {
+    source_id: String,
+    target_id: String,
+    is_zero_one_to_one: bool,
+    color: Option<&'static str>, // ON DELETE CASCADE
+}
+
+// All four directions are supported by DOT
+fn rankdir(direction: &Direction) -> &'static str {
+    match direction {
+        Direction::TB => "TB",
+        Direction::BT => "BT",
+        Direction::LR => "LR",
+        Direction::RL => "RL",
+    }
+}
+
+// Quoted DOT identifier. Ex: "billing.invoice"
+fn id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// Text inside of HTML-like label
+fn html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub struct DotGenerator<'a> {
+    str_templates: TinyTemplate<'a>,
+}
+
+impl<'a> DotGenerator<'a> {
+    pub fn new() -> Result<DotGenerator<'a>, crate::SqlantError> {
+        let mut str_templates = TinyTemplate::new();
+        str_templates.add_template("dot", DOT_TEMPLATE)?;
+        str_templates.add_template("cluster", CLUSTER_TEMPLATE)?;
+        str_templates.add_template("ent", ENTITY_TEMPLATE)?;
+        str_templates.add_template("column", COLUMN_TEMPLATE)?;
+        str_templates.add_template("enum", ENUM_TEMPLATE)?;
+        str_templates.add_template("rel", REL_TEMPLATE)?;
+        str_templates.set_default_formatter(&format_unescaped);
+        Ok(DotGenerator { str_templates })
+    }
+
+    fn column_render(&self, col: &TableColumn) -> Result<String, crate::SqlantError> {
+        let mut markers = vec![];
+        if col.is_pk() {
+            markers.push("PK");
+        }
+        if col.is_fk() {
+            markers.push("FK");
+        }
+        // PK is unique and not null anyway
+        if col.is_unique() && !col.is_pk() {
+            markers.push("UQ");
+        }
+        if col.is_nn() && !col.is_pk() {
+            markers.push("NN");
+        }
+        Ok(self.str_templates.render(
+            "column",
+            &SColumn {
+                markers: markers.join(", "),
+                name: html(&col.name),
+                datatype: html(&col.datatype),
+            },
+        )?)
+    }
+
+    fn entity_render(
+        &self,
+        tbl: &Table,
+        opts: &GeneratorConfigOptions,
+    ) -> Result<String, crate::SqlantError> {
+        let conceptual = opts.conceptual_diagram;
+        let columns = if conceptual {
+            vec![]
+        } else {
+            let sorted_columns = PlantUmlDefaultGenerator::sort_columns(&tbl.columns);
+            [
+                sorted_columns.pks,
+                sorted_columns.fks,
+                sorted_columns.nns,
+                sorted_columns.others,
+            ]
+            .concat()
+            .iter()
+            .map(|col| self.column_render(col))
+            .collect::<Result<Vec<String>, _>>()?
+        };
+        Ok(self.str_templates.render(
+            "ent",
+            &SEntity {
+                id: id(&tbl.name),
+                title: html(&tbl.name),
+                header_color: "lightgrey",
+                is_focused: opts.focus_tables.contains(&tbl.name),
+                tooltip: if opts.draw_comments {
+                    Self::tooltip(tbl.comment.as_deref(), &tbl.columns, conceptual)
+                } else {
+                    None
+                },
+                columns,
+                indexes: if opts.draw_indexes && !conceptual {
+                    tbl.indexes
+                        .iter()
+                        .map(|idx| html(&idx.to_string()))
+                        .collect()
+                } else {
+                    vec![]
+                },
+            },
+        )?)
+    }
+
+    fn view_render(
+        &self,
+        view: &View,
+        opts: &GeneratorConfigOptions,
+    ) -> Result<String, crate::SqlantError> {
+        let conceptual = opts.conceptual_diagram;
+        let columns = if conceptual {
+            vec![]
+        } else {
+            view.columns
+                .iter()
+                .map(|col| self.column_render(col))
+                .collect::<Result<Vec<String>, _>>()?
+        };
+        let kind = if view.materialized {
+            "materialized view"
+        } else {
+            "view"
+        };
+        Ok(self.str_templates.render(
+            "ent",
+            &SEntity {
+                id: id(&view.name),
+                title: format!("{} <I>({kind})</I>", html(&view.name)),
+                header_color: "lightblue",
+                is_focused: false,
+                tooltip: if opts.draw_comments {
+                    Self::tooltip(view.comment.as_deref(), &view.columns, conceptual)
+                } else {
+                    None
+                },
+                columns,
+                indexes: vec![],
+            },
+        )?)
+    }
+
+    // Comment of entity and comments of its columns, one per line
+    fn tooltip(
+        comment: Option<&str>,
+        columns: &[Arc<TableColumn>],
+        conceptual: bool,
+    ) -> Option<String> {
+        let mut lines: Vec<String> = comment.into_iter().map(ToString::to_string).collect();
+        if !conceptual {
+            lines.extend(columns.iter().filter_map(|col| {
+                col.comment
+                    .as_ref()
+                    .map(|comment| format!("{}: {comment}", col.name))
+            }));
+        }
+        (!lines.is_empty()).then(|| {
+            lines
+                .join("\n")
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+        })
+    }
+}
+
+impl ViewGenerator for DotGenerator<'_> {
+    fn generate(
+        &self,
+        sql_erd: SqlERData,
+        opts: &GeneratorConfigOptions,
+    ) -> Result<String, crate::SqlantError> {
+        let entities_of = |schema: Option<&str>| {
+            sql_erd
+                .tables
+                .iter()
+                .filter(|tbl| schema.is_none() || tbl.schema.as_deref() == schema)
+                .map(|tbl| self.entity_render(tbl, opts))
+                .collect::<Result<Vec<String>, crate::SqlantError>>()
+        };
+        let views_of = |schema: Option<&str>| {
+            sql_erd
+                .views
+                .iter()
+                .filter(|view| schema.is_none() || view.schema.as_deref() == schema)
+                .map(|view| self.view_render(view, opts))
+                .collect::<Result<Vec<String>, crate::SqlantError>>()
+        };
+
+        let schemas = sql_erd.schemas();
+        let (clusters, entities, views) = if schemas.len() > 1 {
+            let clusters = schemas
+                .into_iter()
+                .map(|schema| {
+                    Ok(self.str_templates.render(
+                        "cluster",
+                        &SCluster {
+                            name: schema,
+                            entities: entities_of(Some(schema))?,
+                            views: views_of(Some(schema))?,
+                        },
+                    )?)
+                })
+                .collect::<Result<Vec<String>, crate::SqlantError>>()?;
+            (clusters, vec![], vec![])
+        } else {
+            (vec![], entities_of(None)?, views_of(None)?)
+        };
+
+        let foreign_keys: Vec<String> = sql_erd
+            .foreign_keys
+            .iter()
+            .map(|fk| {
+                self.str_templates.render(
+                    "rel",
+                    &SForeignKey {
+                        source_id: id(&fk.source_table.name),
+                        target_id: id(&fk.target_table.name),
+                        is_zero_one_to_one: fk.is_zero_one_to_one,
+                        color: (opts.highlight_cascades
+                            && fk.on_delete == ReferentialAction::Cascade)
+                            .then_some(CASCADE_COLOR),
+                    },
+                )
+            })
+            .collect::<Result<Vec<String>, _>>()?;
+
+        let enums: Vec<String> = if opts.draw_enums {
+            sql_erd
+                .enums
+                .iter()
+                .map(|(name, values)| {
+                    self.str_templates.render(
+                        "enum",
+                        &SEnum {
+                            id: id(&format!("{name} (ENUM)")),
+                            title: format!("{} (ENUM)", html(name)),
+                            values: values.iter().map(|v| html(v)).collect(),
+                        },
+                    )
+                })
+                .collect::<Result<Vec<String>, _>>()?
+        } else {
+            vec![]
+        };
+
+        Ok(self.str_templates.render(
+            "dot",
+            &SDot {
+                direction: opts.direction.as_ref().map(rankdir),
+                clusters,
+                entities,
+                views,
+                enums,
+                foreign_keys,
+            },
+        )?)
+    }
+}
@@ -1,15 +1,123 @@
+use std::path::{Path, PathBuf};
+
 use thiserror::Error;
 
+use crate::{Direction, GeneratorType};
+
 #[derive(Error, Debug)]
 pub enum SqlantError {
+    #[error("Failed to connect to database: {0}")]
+    Connection(#[source] Box<SqlantError>),
+    #[error("Schema {schema} doesn't exist")]
+    SchemaNotFound { schema: String },
+    #[error("Table {name} doesn't exist")]
+    TableNotFound { name: String },
+    // Name matches tables of several schemas
+    #[error("Table {name} is ambiguous: {}", candidates.join(", "))]
+    AmbiguousTable {
+        name: String,
+        candidates: Vec<String>,
+    },
+    #[error("Unsupported version {found} of JSON format (expected {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+    #[error("Invalid connection string: {message}")]
+    InvalidConnectionString { message: String },
+    #[error("Failed to parse {input}: {message}")]
+    Parse { input: String, message: String },
+    // SQL of failed query is kept for debugging
+    #[error("Query failed: {source}")]
+    Query {
+        query: String,
+        #[source]
+        source: Box<SqlantError>,
+    },
+    // Field can't be named `source`, thiserror treats it as source error
+    #[error(
+        "Foreign key {source_table}({}) references {target_table}({}) which doesn't exist",
+        source_columns.join(", "),
+        target_columns.join(", ")
+    )]
+    DanglingForeignKey {
+        source_table: String,
+        source_columns: Vec<String>,
+        target_table: String,
+        target_columns: Vec<String>,
+    },
+    #[error("{direction} direction is not supported by {generator}")]
+    UnsupportedDirection {
+        generator: GeneratorType,
+        direction: Direction,
+    },
+    #[error("Unsupported option: {0}")]
+    UnsupportedOption(String),
+    // Key is `profiles.<profile>.<option>` for options of profile
+    #[error(
+        "{}{}: {message}",
+        path.display(),
+        key.as_ref().map(|key| format!(": {key}")).unwrap_or_default()
+    )]
+    Config {
+        path: PathBuf,
+        key: Option<String>,
+        message: String,
+    },
+    #[error("IO error {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
     #[error("Postgres error {0}")]
     Postgres(#[from] tokio_postgres::Error),
+    #[error("SQLite error {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("MySQL error {0}")]
+    MySql(#[from] mysql_async::Error),
     #[error("TlsConnector error {0}")]
     TlsConnector(#[from] native_tls::Error),
     #[error("Template error {0}")]
     Template(#[from] tinytemplate::error::Error),
-    #[error("PsqlErdLoader error {0}")]
-    PsqlErdLoader(String),
-    #[error("Generator error {0}")]
-    Generator(String),
+    #[error("JSON error {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Pattern error {0}")]
+    Pattern(#[from] glob::PatternError),
+    // State that shouldn't happen (e.g. unexpected result of catalog query)
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl SqlantError {
+    /// Error of SQL query. Ex: `.map_err(SqlantError::query(QUERY))?`
+    pub(crate) fn query<E: Into<SqlantError>>(query: &str) -> impl FnOnce(E) -> SqlantError + '_ {
+        move |e| SqlantError::Query {
+            query: query.trim().to_string(),
+            source: Box::new(e.into()),
+        }
+    }
+
+    /// Error of file operation. Ex: `.map_err(SqlantError::io(path))?`
+    pub(crate) fn io(path: &Path) -> impl FnOnce(std::io::Error) -> SqlantError + '_ {
+        move |source| SqlantError::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    pub(crate) fn dangling_foreign_key(
+        source_table: &str,
+        source_columns: &[String],
+        target_table: &str,
+        target_columns: &[String],
+    ) -> SqlantError {
+        SqlantError::DanglingForeignKey {
+            source_table: source_table.to_string(),
+            source_columns: source_columns.to_vec(),
+            target_table: target_table.to_string(),
+            target_columns: target_columns.to_vec(),
+        }
+    }
+
+    pub(crate) fn connection<E: Into<SqlantError>>(e: E) -> SqlantError {
+        SqlantError::Connection(Box::new(e.into()))
+    }
 }
@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::Arc;
+
+use strum_macros::{Display, EnumString};
+
+use crate::sql_entities::{SqlERData, Table, View};
+use crate::SqlantError;
+
+/// Direction of foreign keys to follow from root tables.
+/// FK from `customer_order` to `customer` is outgoing for `customer_order`
+/// and incoming for `customer`.
+#[derive(Clone, Debug, Default, Display, EnumString, Eq, PartialEq, PartialOrd, Ord)]
+#[strum(serialize_all = "lowercase")]
+pub enum FocusDirection {
+    Outgoing,
+    Incoming,
+    #[default]
+    Both,
+}
+
+/// Neighbourhood of root tables: tables reachable from roots
+/// by at most `depth` foreign keys in `direction`.
+#[derive(Clone, Debug)]
+pub struct Focus {
+    // Name of table or `schema.table`
+    pub roots: Vec<String>,
+    pub depth: usize,
+    pub direction: FocusDirection,
+}
+
+fn is_table(tbl: &Table, name: &str) -> bool {
+    tbl.name == name
+        || tbl
+            .schema
+            .as_deref()
+            .and_then(|schema| tbl.name.strip_prefix(&format!("{schema}.")))
+            .is_some_and(|short_name| short_name == name)
+}
+
+impl Focus {
+    /// Names of root tables in `sql_erd`.
+    /// Fails if root table doesn't exist or name is ambiguous (the same table in several schemas).
+    pub fn root_names(&self, sql_erd: &SqlERData) -> Result<Vec<String>, SqlantError> {
+        self.roots
+            .iter()
+            .map(|root| {
+                let tables: Vec<&str> = sql_erd
+                    .tables
+                    .iter()
+                    .filter(|tbl| is_table(tbl, root))
+                    .map(|tbl| tbl.name.as_str())
+                    .collect();
+                match tables.as_slice() {
+                    [name] => Ok(name.to_string()),
+                    [] => Err(SqlantError::TableNotFound { name: root.clone() }),
+                    _ => Err(SqlantError::AmbiguousTable {
+                        name: root.clone(),
+                        candidates: tables.iter().map(|name| name.to_string()).collect(),
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// Names of tables in neighbourhood with their distance (number of hops) from roots
+    pub fn neighbourhood(
+        &self,
+        sql_erd: &SqlERData,
+    ) -> Result<BTreeMap<String, usize>, SqlantError> {
+        // Adjacency list of FKs in direction of traversal
+        let mut neighbours: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for fk in &sql_erd.foreign_keys {
+            let (source, target) = (fk.source_table.name.as_str(), fk.target_table.name.as_str());
+            if self.direction != FocusDirection::Incoming {
+                neighbours.entry(source).or_default().insert(target);
+            }
+            if self.direction != FocusDirection::Outgoing {
+                neighbours.entry(target).or_default().insert(source);
+            }
+        }
+
+        let mut res: BTreeMap<String, usize> = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        for root in self.root_names(sql_erd)? {
+            if !res.contains_key(&root) {
+                res.insert(root.clone(), 0);
+                queue.push_back(root);
+            }
+        }
+        // Breadth-first search, so every table gets the shortest distance
+        while let Some(name) = queue.pop_front() {
+            let distance = res[&name];
+            if distance == self.depth {
+                continue;
+            }
+            for &neighbour in neighbours.get(name.as_str()).into_iter().flatten() {
+                if !res.contains_key(neighbour) {
+                    res.insert(neighbour.to_string(), distance + 1);
+                    queue.push_back(neighbour.to_string());
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// Keeps only tables of neighbourhood and foreign keys between them.
+    /// Views are kept if all entities they depend on are kept.
+    pub fn apply(&self, sql_erd: SqlERData) -> Result<SqlERData, SqlantError> {
+        let neighbourhood = self.neighbourhood(&sql_erd)?;
+        let SqlERData {
+            tables,
+            foreign_keys,
+            enums,
+            views,
+        } = sql_erd;
+
+        // Views can depend on other views, so they are added until nothing changes
+        let mut names: BTreeSet<String> = neighbourhood.into_keys().collect();
+        let mut kept_views: Vec<Arc<View>> = vec![];
+        let mut rest = views;
+        loop {
+            let (kept, not_kept): (Vec<_>, Vec<_>) = rest.into_iter().partition(|view| {
+                !view.depends_on.is_empty()
+                    && view.depends_on.iter().all(|name| names.contains(name))
+            });
+            rest = not_kept;
+            if kept.is_empty() {
+                break;
+            }
+            names.extend(kept.iter().map(|view| view.name.clone()));
+            kept_views.extend(kept);
+        }
+        let views = kept_views
+            .into_iter()
+            .map(|view| {
+                if view.used_columns.keys().all(|name| names.contains(name)) {
+                    return view;
+                }
+                let mut view = Arc::unwrap_or_clone(view);
+                view.depends_on.retain(|name| names.contains(name));
+                view.used_columns.retain(|name, _| names.contains(name));
+                Arc::new(view)
+            })
+            .collect();
+
+        Ok(SqlERData {
+            tables: tables
+                .into_iter()
+                .filter(|tbl| names.contains(&tbl.name))
+                .collect(),
+            foreign_keys: foreign_keys
+                .into_iter()
+                .filter(|fk| {
+                    names.contains(&fk.source_table.name) && names.contains(&fk.target_table.name)
+                })
+                .collect(),
+            enums,
+            views,
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::json_model::{JsonERData, JSON_FORMAT_VERSION};
+use crate::sql_entities::{ForeignKey, SqlERData, SqlERDataLoader, Table, TableColumn};
+use crate::SqlantError;
+
+// Loads model previously exported with `-o json`
+pub struct JsonERDLoader {
+    json: String,
+}
+
+impl JsonERDLoader {
+    pub fn new(json: String) -> JsonERDLoader {
+        JsonERDLoader { json }
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<JsonERDLoader, SqlantError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(SqlantError::io(path))?;
+        Ok(Self::new(json))
+    }
+}
+
+// Ex: customer_order.customer_id
+fn find_columns(tbl: &Table, column_names: &[String]) -> Option<Vec<Arc<TableColumn>>> {
+    column_names
+        .iter()
+        .map(|name| tbl.columns.iter().find(|col| &col.name == name).cloned())
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl SqlERDataLoader for JsonERDLoader {
+    async fn load_erd_data(&mut self) -> Result<SqlERData, SqlantError> {
+        let json_erd: JsonERData = serde_json::from_str(&self.json)?;
+        if json_erd.version != JSON_FORMAT_VERSION {
+            return Err(SqlantError::UnsupportedVersion {
+                found: json_erd.version,
+                supported: JSON_FORMAT_VERSION,
+            });
+        }
+
+        let tables: Vec<Arc<Table>> = json_erd
+            .tables
+            .into_iter()
+            .map(|json_tbl| {
+                // Table::new computes has_composite_pk
+                let mut tbl = Table::new(
+                    json_tbl.name,
+                    json_tbl.columns.into_iter().map(Arc::new).collect(),
+                );
+                tbl.schema = json_tbl.schema;
+                tbl.indexes = json_tbl.indexes;
+                tbl.comment = json_tbl.comment;
+                Arc::new(tbl)
+            })
+            .collect();
+
+        let find_table = |name: &str| tables.iter().find(|tbl| tbl.name == name);
+        let foreign_keys = json_erd
+            .foreign_keys
+            .iter()
+            .map(|fk| {
+                let fk_data = || {
+                    let source_table = find_table(&fk.source_table)?;
+                    let target_table = find_table(&fk.target_table)?;
+                    // ForeignKey::new computes is_zero_one_to_one
+                    Some(
+                        ForeignKey::new(
+                            Arc::clone(source_table),
+                            find_columns(source_table, &fk.source_columns)?,
+                            Arc::clone(target_table),
+                            find_columns(target_table, &fk.target_columns)?,
+                        )
+                        .with_name(fk.name.clone())
+                        .with_actions(fk.on_delete, fk.on_update)
+                        .with_deferrable(fk.deferrable, fk.initially_deferred)
+                        .with_match_type(fk.match_type),
+                    )
+                };
+                fk_data().ok_or_else(|| {
+                    SqlantError::dangling_foreign_key(
+                        &fk.source_table,
+                        &fk.source_columns,
+                        &fk.target_table,
+                        &fk.target_columns,
+                    )
+                })
+            })
+            .collect::<Result<Vec<ForeignKey>, SqlantError>>()?;
+
+        Ok(SqlERData {
+            tables,
+            foreign_keys,
+            enums: json_erd.enums,
+            views: json_erd.views.into_iter().map(Arc::new).collect(),
+        })
+    }
+}
@@ -0,0 +1,16 @@
+use super::sql_entities::SqlERData;
+use crate::json_model::JsonERData;
+use crate::{GeneratorConfigOptions, ViewGenerator};
+
+// Exports the whole model, diagram options are ignored
+pub struct JsonGenerator;
+
+impl ViewGenerator for JsonGenerator {
+    fn generate(
+        &self,
+        sql_erd: SqlERData,
+        _opts: &GeneratorConfigOptions,
+    ) -> Result<String, crate::SqlantError> {
+        Ok(serde_json::to_string_pretty(&JsonERData::from(&sql_erd))?)
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::sql_entities::{
+    MatchType, ReferentialAction, SqlERData, Table, TableColumn, TableIndex, View,
+};
+use serde::{Deserialize, Serialize};
+
+// Version of JSON format. It has to be increased on every incompatible change
+// of JsonERData or entities it contains.
+pub const JSON_FORMAT_VERSION: u32 = 1;
+
+// JSON representation of SqlERData.
+// Tables and columns are referenced by names instead of Arc pointers.
+// Derived data (Table::has_composite_pk, ForeignKey::is_zero_one_to_one)
+// is not stored, it's computed on loading.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct JsonERData {
+    pub version: u32,
+    pub tables: Vec<JsonTable>,
+    pub foreign_keys: Vec<JsonForeignKey>,
+    pub enums: BTreeMap<String, Vec<String>>,
+    pub views: Vec<View>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct JsonTable {
+    // Qualified as `schema.table` if several schemas are loaded
+    pub name: String,
+    pub schema: Option<String>,
+    pub columns: Vec<TableColumn>,
+    pub indexes: Vec<TableIndex>,
+    pub comment: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct JsonForeignKey {
+    pub source_table: String,
+    pub source_columns: Vec<String>,
+    pub target_table: String,
+    pub target_columns: Vec<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub on_delete: ReferentialAction,
+    #[serde(default)]
+    pub on_update: ReferentialAction,
+    #[serde(default)]
+    pub deferrable: bool,
+    #[serde(default)]
+    pub initially_deferred: bool,
+    #[serde(default)]
+    pub match_type: MatchType,
+}
+
+impl From<&Table> for JsonTable {
+    fn from(tbl: &Table) -> Self {
+        JsonTable {
+            name: tbl.name.clone(),
+            schema: tbl.schema.clone(),
+            columns: tbl.columns.iter().map(|c| c.as_ref().clone()).collect(),
+            indexes: tbl.indexes.clone(),
+            comment: tbl.comment.clone(),
+        }
+    }
+}
+
+impl From<&SqlERData> for JsonERData {
+    fn from(sql_erd: &SqlERData) -> Self {
+        let column_names =
+            |columns: &[Arc<TableColumn>]| columns.iter().map(|c| c.name.clone()).collect();
+        JsonERData {
+            version: JSON_FORMAT_VERSION,
+            tables: sql_erd
+                .tables
+                .iter()
+                .map(|tbl| tbl.as_ref().into())
+                .collect(),
+            foreign_keys: sql_erd
+                .foreign_keys
+                .iter()
+                .map(|fk| JsonForeignKey {
+                    source_table: fk.source_table.name.clone(),
+                    source_columns: column_names(&fk.source_columns),
+                    target_table: fk.target_table.name.clone(),
+                    target_columns: column_names(&fk.target_columns),
+                    name: fk.name.clone(),
+                    on_delete: fk.on_delete,
+                    on_update: fk.on_update,
+                    deferrable: fk.deferrable,
+                    initially_deferred: fk.initially_deferred,
+                    match_type: fk.match_type,
+                })
+                .collect(),
+            enums: sql_erd.enums.clone(),
+            views: sql_erd.views.iter().map(|v| v.as_ref().clone()).collect(),
+        }
+    }
+}
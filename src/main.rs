@@ -1,45 +1,425 @@
-use std::str::FromStr;
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+};
 
-use clap::ArgMatches;
-use sqlant::{get_generator, lookup_loader, Direction, GeneratorConfigOptions, GeneratorType};
+use clap::error::ErrorKind;
+use config::{Args, SOURCE_ARGS};
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader,
+    diff::SqlERDiff,
+    focus::{Focus, FocusDirection},
+    get_generator,
+    json_erd_loader::JsonERDLoader,
+    lookup_loader, lookup_source_loader,
+    output::write_file,
+    partition::{load_groups, split_by_components, split_by_groups, write_partitions},
+    sql_entities::{SqlERData, SqlERDataLoader},
+    table_filter::{FilteredERDLoader, TableFilter},
+    tls::{SslMode, TlsConfig},
+    Direction, GeneratorConfigOptions, GeneratorType, SqlantError,
+};
 
 mod cli;
+mod config;
 
-fn get_arg(args: &ArgMatches, arg_name: &str) -> String {
-    args.get_one::<String>(arg_name).unwrap().to_string()
+// Exit codes. Invalid command line arguments are reported by clap with code 2
+const EXIT_ERROR: u8 = 1;
+const EXIT_INVALID_ARGUMENTS: u8 = 2;
+const EXIT_CONNECTION_FAILED: u8 = 3;
+const EXIT_SCHEMA_NOT_FOUND: u8 = 4;
+const EXIT_UNSUPPORTED_OPTION: u8 = 5;
+const EXIT_GENERATION_FAILED: u8 = 6;
+
+/// Error with description of failed step. Ex: Failed to load schema: Schema billing doesn't exist
+struct CliError {
+    context: String,
+    error: SqlantError,
 }
 
-#[tokio::main]
-async fn main() {
-    let args = cli::parse();
-
-    let mut s = lookup_loader(
-        &get_arg(&args, "connection_string"),
-        get_arg(&args, "schema"),
-    )
-    .await
-    .unwrap();
-    let erd = s.load_erd_data().await.unwrap();
-    let output_arg = get_arg(&args, "output");
-    let generator_type =
-        GeneratorType::from_str(&output_arg).expect("Generator type {output_arg} isn't supported");
-    let rndr = get_generator(generator_type).unwrap();
-    let direction_arg = args.get_one::<String>("direction");
-    let direction = direction_arg.map(|dir| {
-        Direction::from_str(dir).unwrap_or_else(|_| panic!("Direction {dir} isn't supported"))
-    });
-    let result = rndr
-        .generate(
-            erd,
-            &GeneratorConfigOptions {
-                not_null: args.get_flag("not_null"),
-                draw_enums: args.get_flag("enums"),
-                draw_legend: args.get_flag("legend"),
-                inline_puml_lib: args.get_flag("inline-puml-lib"),
-                conceptual_diagram: args.get_flag("conceptual"),
-                direction,
-            },
+impl CliError {
+    fn exit_code(&self) -> u8 {
+        match &self.error {
+            SqlantError::Connection(_) => EXIT_CONNECTION_FAILED,
+            SqlantError::SchemaNotFound { .. } => EXIT_SCHEMA_NOT_FOUND,
+            SqlantError::Config { .. } | SqlantError::InvalidConnectionString { .. } => {
+                EXIT_INVALID_ARGUMENTS
+            }
+            SqlantError::UnsupportedOption(_)
+            | SqlantError::UnsupportedDirection { .. }
+            | SqlantError::UnsupportedVersion { .. }
+            | SqlantError::Pattern(_)
+            | SqlantError::TableNotFound { .. }
+            | SqlantError::AmbiguousTable { .. } => EXIT_UNSUPPORTED_OPTION,
+            SqlantError::Template(_) => EXIT_GENERATION_FAILED,
+            _ => EXIT_ERROR,
+        }
+    }
+
+    // Messages of sources are added if they aren't included into message of error
+    fn message(&self) -> String {
+        let mut message = format!("{}: {}", self.context, self.error);
+        let mut source = std::error::Error::source(&self.error);
+        while let Some(e) = source {
+            let source_message = e.to_string();
+            if !message.contains(&source_message) {
+                message = format!("{message}: {source_message}");
+            }
+            source = e.source();
+        }
+        message
+    }
+
+    fn report(&self, verbose: bool) {
+        eprintln!("error: {}", self.message());
+        if verbose {
+            if let Some(query) = failed_query(&self.error) {
+                eprintln!("failed query:\n{query}");
+            }
+        } else if failed_query(&self.error).is_some() {
+            eprintln!("run with --verbose to print failed query");
+        }
+    }
+}
+
+fn failed_query(error: &SqlantError) -> Option<&str> {
+    match error {
+        SqlantError::Query { query, .. } => Some(query),
+        SqlantError::Connection(e) => failed_query(e),
+        _ => None,
+    }
+}
+
+trait Context<T> {
+    fn context(self, context: impl FnOnce() -> String) -> Result<T, CliError>;
+}
+
+impl<T, E: Into<SqlantError>> Context<T> for Result<T, E> {
+    fn context(self, context: impl FnOnce() -> String) -> Result<T, CliError> {
+        self.map_err(|e| CliError {
+            context: context(),
+            error: e.into(),
+        })
+    }
+}
+
+fn unsupported_option(message: String) -> CliError {
+    CliError {
+        context: "Invalid arguments".to_string(),
+        error: SqlantError::UnsupportedOption(message),
+    }
+}
+
+fn stdout_error(source: io::Error) -> SqlantError {
+    SqlantError::Io {
+        path: PathBuf::from("stdout"),
+        source,
+    }
+}
+
+fn get_arg(args: &Args, arg_name: &str) -> String {
+    args.get_one(arg_name).unwrap()
+}
+
+fn get_args(args: &Args, arg_name: &str) -> Vec<String> {
+    args.get_many(arg_name).unwrap()
+}
+
+// Options which are required by other options can be missing in config file
+fn get_required_arg(args: &Args, arg_name: &str, required_by: &str) -> Result<String, CliError> {
+    args.get_one(arg_name).ok_or_else(|| {
+        unsupported_option(format!(
+            "--{} is required by --{required_by}",
+            arg_name.replace('_', "-")
+        ))
+    })
+}
+
+// Options can be set in config file, so clap doesn't check which of them are used together
+fn check_args(args: &Args) -> Result<(), CliError> {
+    match args.get_one("split") {
+        Some(split) => {
+            if args.is_set("out") {
+                return Err(unsupported_option(
+                    "--split can't be used with --out".to_string(),
+                ));
+            }
+            get_required_arg(args, "out_dir", "split")?;
+            if split == "groups" {
+                get_required_arg(args, "groups", "split groups")?;
+            }
+        }
+        // out_dir of config file is ignored without --split
+        None if args.is_command_line("out_dir") => {
+            return Err(unsupported_option(
+                "--split is required by --out-dir".to_string(),
+            ))
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+fn check_source_args(args: &Args) -> Result<(), CliError> {
+    let sources: Vec<String> = SOURCE_ARGS
+        .into_iter()
+        .filter(|id| args.is_set(id))
+        .map(|id| match id {
+            "connection_string" => "connection string".to_string(),
+            _ => format!("--{}", id.replace('_', "-")),
+        })
+        .collect();
+    if sources.len() > 1 {
+        return Err(unsupported_option(format!(
+            "{} can't be used together",
+            sources.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn get_table_filter(args: &Args) -> Result<TableFilter, CliError> {
+    let patterns = |arg_name| args.get_many(arg_name).unwrap_or_default();
+    let include: Vec<String> = patterns("include");
+    TableFilter::new(&include, &patterns("exclude"))
+        .context(|| "Invalid --include or --exclude pattern".to_string())
+}
+
+fn get_focus(args: &Args) -> Result<Option<Focus>, CliError> {
+    let Some(roots) = args.get_many("focus") else {
+        return Ok(None);
+    };
+    let direction = get_arg(args, "focus_direction");
+    Ok(Some(Focus {
+        roots,
+        depth: args.get_usize("depth").unwrap(),
+        direction: FocusDirection::from_str(&direction).map_err(|_| {
+            unsupported_option(format!("focus direction {direction} isn't supported"))
+        })?,
+    }))
+}
+
+// Options which aren't set are taken from connection string
+fn get_tls_config(args: &Args) -> Result<TlsConfig, CliError> {
+    let path = |arg_name| args.get_one(arg_name).map(PathBuf::from);
+    Ok(TlsConfig {
+        mode: args
+            .get_one("sslmode")
+            .map(|mode| {
+                SslMode::from_str(&mode)
+                    .map_err(|_| unsupported_option(format!("sslmode {mode} isn't supported")))
+            })
+            .transpose()?,
+        root_cert: path("sslrootcert"),
+        client_cert: path("sslcert"),
+        client_key: path("sslkey"),
+        client_cert_password: None,
+    })
+}
+
+fn get_generator_type(args: &Args) -> Result<GeneratorType, CliError> {
+    let output_arg = get_arg(args, "output");
+    GeneratorType::from_str(&output_arg)
+        .map_err(|_| unsupported_option(format!("generator type {output_arg} isn't supported")))
+}
+
+// Generator type of every --out file or of stdout if there are no --out files.
+// Generator type is inferred from extension of file if -o isn't set explicitly.
+fn get_outputs(args: &Args) -> Result<Vec<(GeneratorType, Option<PathBuf>)>, CliError> {
+    let Some(paths) = args.get_many("out") else {
+        return Ok(vec![(get_generator_type(args)?, None)]);
+    };
+    let is_output_set = args.is_set("output");
+    paths
+        .into_iter()
+        .map(|path| {
+            let path = PathBuf::from(path);
+            let generator_type = if is_output_set {
+                get_generator_type(args)?
+            } else {
+                GeneratorType::from_extension(&path).ok_or_else(|| {
+                    unsupported_option(format!(
+                        "output format of {} can't be inferred from extension, set it with -o",
+                        path.display()
+                    ))
+                })?
+            };
+            Ok((generator_type, Some(path)))
+        })
+        .collect()
+}
+
+fn generate(args: &Args, mut erd: SqlERData, diff: Option<SqlERDiff>) -> Result<(), CliError> {
+    let outputs = get_outputs(args)?;
+    if diff.is_some()
+        && outputs.iter().any(|(generator_type, _)| {
+            ![GeneratorType::PlantUML, GeneratorType::Mermaid].contains(generator_type)
+        })
+    {
+        return Err(unsupported_option(
+            "diff is supported only for plantuml and mermaid outputs".to_string(),
+        ));
+    }
+    let mut focus_tables = vec![];
+    if let Some(focus) = get_focus(args)? {
+        let context = || "Failed to select neighbourhood of --focus tables".to_string();
+        focus_tables = focus.root_names(&erd).context(context)?;
+        erd = focus.apply(erd).context(context)?;
+    }
+    let direction = args
+        .get_one("direction")
+        .map(|dir| {
+            Direction::from_str(&dir)
+                .map_err(|_| unsupported_option(format!("direction {dir} isn't supported")))
+        })
+        .transpose()?;
+    let opts = GeneratorConfigOptions {
+        not_null: args.get_flag("not_null"),
+        draw_enums: args.get_flag("enums"),
+        draw_legend: args.get_flag("legend"),
+        draw_lineage: args.get_flag("lineage"),
+        highlight_cascades: args.get_flag("cascades"),
+        inline_puml_lib: args.get_flag("inline-puml-lib"),
+        conceptual_diagram: args.get_flag("conceptual"),
+        draw_indexes: args.get_flag("indexes"),
+        draw_comments: args.get_flag("comments"),
+        direction,
+        diff,
+        focus_tables,
+    };
+    if let Some(split) = args.get_one("split") {
+        let generator_type = get_generator_type(args)?;
+        let partitions = match split.as_str() {
+            "groups" => {
+                let groups_file = get_arg(args, "groups");
+                let groups = load_groups(&groups_file)
+                    .context(|| format!("Failed to load groups from {groups_file}"))?;
+                split_by_groups(&erd, &groups)
+            }
+            _ => split_by_components(&erd),
+        };
+        let out_dir = get_arg(args, "out_dir");
+        let generator = get_generator(generator_type.clone())
+            .context(|| format!("Failed to create {generator_type} generator"))?;
+        return write_partitions(
+            partitions,
+            generator.as_ref(),
+            &opts,
+            Path::new(&out_dir),
+            generator_type.extension(),
+        )
+        .context(|| format!("Failed to write {generator_type} diagrams into {out_dir}"));
+    }
+    // Data is loaded once for all outputs
+    for (generator_type, path) in outputs {
+        let result = get_generator(generator_type.clone())
+            .and_then(|generator| generator.generate(erd.clone(), &opts))
+            .context(|| format!("Failed to generate {generator_type} diagram"))?;
+        match path {
+            Some(path) => write_file(&path, &(result + "\n"))
+                .context(|| format!("Failed to write {}", path.display()))?,
+            None => writeln!(io::stdout(), "{result}")
+                .map_err(stdout_error)
+                .context(|| "Failed to write to stdout".to_string())?,
+        }
+    }
+    Ok(())
+}
+
+async fn diff(args: &Args) -> Result<(), CliError> {
+    check_args(args)?;
+    let schema_names = get_args(args, "schema");
+    let table_filter = get_table_filter(args)?;
+    let tls_config = get_tls_config(args)?;
+    let mut erds = vec![];
+    for source in ["old", "new"] {
+        let context = || format!("Failed to load {source} schema");
+        let mut loader = lookup_source_loader(
+            &get_arg(args, source),
+            schema_names.clone(),
+            table_filter.clone(),
+            tls_config.clone(),
         )
-        .unwrap();
-    println!("{result}")
+        .await
+        .context(context)?;
+        erds.push(loader.load_erd_data().await.context(context)?);
+    }
+    let (old_erd, new_erd) = (&erds[0], &erds[1]);
+    let diff = SqlERDiff::new(old_erd, new_erd);
+    if args.get_flag("summary") {
+        return write!(io::stdout(), "{}", diff.summary())
+            .map_err(stdout_error)
+            .context(|| "Failed to write to stdout".to_string());
+    }
+    let erd = diff.merge(old_erd, new_erd);
+    generate(args, erd, Some(diff))
+}
+
+async fn run(args: &Args) -> Result<(), CliError> {
+    check_source_args(args)?;
+    check_args(args)?;
+    let table_filter = get_table_filter(args)?;
+    let context = || "Failed to load schema".to_string();
+    let mut s: Box<dyn SqlERDataLoader> =
+        match (args.get_many("from_file"), args.get_one("from_json")) {
+            (Some(files), _) => Box::new(FilteredERDLoader::new(
+                DdlERDLoader::from_files(&files, get_args(args, "schema")).context(context)?,
+                table_filter,
+            )),
+            (None, Some(file)) => Box::new(FilteredERDLoader::new(
+                JsonERDLoader::from_file(file).context(context)?,
+                table_filter,
+            )),
+            (None, None) => lookup_loader(
+                &get_arg(args, "connection_string"),
+                get_args(args, "schema"),
+                table_filter,
+                get_tls_config(args)?,
+            )
+            .await
+            .context(context)?,
+        };
+    let erd = s.load_erd_data().await.context(context)?;
+    generate(args, erd, None)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let command = cli::command();
+    let args = match Args::load(&command, command.clone().get_matches()) {
+        Ok(args) => args,
+        Err(error) => {
+            let e = CliError {
+                context: "Invalid config file".to_string(),
+                error,
+            };
+            e.report(false);
+            return ExitCode::from(e.exit_code());
+        }
+    };
+    let (res, verbose) = match args.subcommand() {
+        Some(("diff", diff_args)) => (diff(&diff_args).await, diff_args.get_flag("verbose")),
+        _ => {
+            // Source of schema can be set in config file, so clap doesn't check it
+            if !SOURCE_ARGS.iter().any(|id| args.is_set(id)) {
+                command
+                    .clone()
+                    .error(
+                        ErrorKind::MissingRequiredArgument,
+                        "connection string, --from-file or --from-json is required",
+                    )
+                    .exit();
+            }
+            (run(&args).await, args.get_flag("verbose"))
+        }
+    };
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            e.report(verbose);
+            ExitCode::from(e.exit_code())
+        }
+    }
 }
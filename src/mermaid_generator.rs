@@ -1,31 +1,52 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use super::sql_entities::{SqlERData, Table, TableColumn};
+use super::sql_entities::{ForeignKey, SqlERData, Table, TableColumn, View};
+use crate::diff::Change;
 use crate::{Direction, GeneratorConfigOptions, ViewGenerator};
 use serde::Serialize;
 use tinytemplate::{format_unescaped, TinyTemplate};
 
 static MERMAID_TEMPLATE: &str = r#"erDiagram{{ if direction }}
 direction {direction}{{ endif }}
-{{ for ent in entities}}{ent}{{ endfor }}
+{{ for ent in entities}}{ent}{{ endfor }}{{ for view in views}}{view}{{ endfor }}
 {{ for en in enums}}{en}{{ endfor }}
-{{ for fk in foreign_keys}}{fk}{{ endfor }}
-"#;
+{{ for fk in foreign_keys}}{fk}{{ endfor }}{{ for dep in dependencies}}{dep}{{ endfor }}{{ if legend }}
+{legend}{{ endif }}{{ if diff }}
+classDef added fill:#C8F7C5
+classDef removed fill:#F7C5C5
+classDef changed fill:#FFE0A3
+{{ endif }}{{ if focus_tables }}
+classDef focus stroke:#FF8C00,stroke-width:3px
+class {focus_tables} focus
+{{ endif }}"#;
 
-static ENTITY_TEMPLATE: &str = "{name} \\{\n{pks}{fks}{others}}\n";
+static ENTITY_TEMPLATE: &str =
+    "{name}{{ if change }}:::{change}{{ endif }} \\{\n{pks}{fks}{others}}\n{{ if comment }}%% {comment}\n{{ endif }}{{ for idx in indexes }}%% {idx}\n{{ endfor }}";
 
-static COLUMN_TEMPLATE: &str = "    {col.datatype} {col.name}{{ if is_pk_or_fk }} {{ endif }}{{ if is_pk }}PK,{{ endif }}{{ if is_fk }}FK{{ endif }}";
+static COLUMN_TEMPLATE: &str = "    {col.datatype} {col.name}{{ if is_key }} {{ endif }}{{ if is_pk }}PK,{{ endif }}{{ if is_fk }}FK,{{ endif }}{{ if is_uq }}UK{{ endif }}";
 
-static REL_TEMPLATE: &str = "{source_table_name} {{ if is_zero_one_to_one }}|o--||{{else}}}o--||{{ endif }} {target_table_name}: \"\"\n";
+static REL_TEMPLATE: &str = "{source_table_name} {{ if is_many_to_many }}}o--o\\{{{ else }}{{ if is_zero_one_to_one }}|o--||{{else}}}o--||{{ endif }}{{ endif }} {target_table_name}: \"{label}\"\n";
 
-const ENUM_TEMPLATE: &str = "\"{name} (ENUM)\" \\{\n{{ for v in values}}    {v} _\n{{ endfor }}}";
+// Dashed (non-identifying) relationship from view to table or view that it reads from.
+// Label is list of used columns if lineage is drawn
+static DEPENDENCY_TEMPLATE: &str = "{view_name} }o..o\\{ {source_name}: \"{{ if columns }}{columns}{{ else }}depends on{{ endif }}\"\n";
+
+// Keys are written into key column like in entities, other markers into comment
+// Quoted name with space can't be the same as name of table (unless it's quoted in SQL too)
+static LEGEND_TEMPLATE: &str = "\"sqlant legend\" \\{\n{{ for row in rows }}    _ _{{ if row.key }} {row.key}{{ endif }} \"{row.description}\"\n{{ endfor }}}\n";
+
+const ENUM_TEMPLATE: &str = "\"{name} (ENUM)\"{{ if change }}:::{change}{{ endif }} \\{\n{{ for v in values}}    {v} _\n{{ endfor }}}";
 
 #[derive(Serialize)]
 struct SEntity {
     name: String,
-    pks: String,    // Columns that contain PK
-    fks: String,    // Columns that contain FK and don't contain PK
-    others: String, // Columns that don't contain both PK and FK
+    change: Option<&'static str>, // Class of changed entity in diff diagram
+    pks: String,                  // Columns that contain PK
+    fks: String,                  // Columns that contain FK and don't contain PK
+    others: String,               // Columns that don't contain both PK and FK
+    comment: Option<String>,      // Rendered as comment after entity
+    indexes: Vec<String>,         // Rendered as comments after entity
 }
 
 #[derive(Serialize)]
@@ -33,7 +54,8 @@ struct SColumn<'a> {
     col: &'a TableColumn,
     is_fk: bool,
     is_pk: bool,
-    is_pk_or_fk: bool,
+    is_uq: bool,
+    is_key: bool,
     is_nn: bool,
 }
 
@@ -59,9 +81,25 @@ impl From<&Direction> for SDirection {
 #[derive(Serialize)]
 struct SMermaid {
     direction: Option<SDirection>,
+    diff: bool,
+    focus_tables: Option<String>, // Ex: customer,customer_order
     entities: Vec<String>,
+    views: Vec<String>,
     enums: Vec<String>,
     foreign_keys: Vec<String>,
+    dependencies: Vec<String>,
+    legend: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SLegendRow {
+    key: Option<&'static str>,
+    description: &'static str,
+}
+
+#[derive(Serialize)]
+struct SLegend {
+    rows: Vec<SLegendRow>,
 }
 
 #[derive(Serialize)]
@@ -69,12 +107,22 @@ struct SForeignKey {
     source_table_name: String,
     target_table_name: String,
     is_zero_one_to_one: bool,
+    is_many_to_many: bool, // Collapsed junction table in conceptual diagram
+    label: String,         // Name of FK, its non-default clauses and change
+}
+
+#[derive(Serialize)]
+struct SDependency {
+    view_name: String,
+    source_name: String,
+    columns: Option<String>, // Ex: customer_id, total_price
 }
 
 #[derive(Serialize)]
 struct SEnum {
     name: String,
     values: Vec<String>,
+    change: Option<&'static str>,
 }
 
 pub struct MermaidGenerator<'a> {
@@ -88,23 +136,27 @@ impl<'a> MermaidGenerator<'a> {
         str_templates.add_template("column", COLUMN_TEMPLATE)?;
         str_templates.add_template("ent", ENTITY_TEMPLATE)?;
         str_templates.add_template("rel", REL_TEMPLATE)?;
+        str_templates.add_template("dependency", DEPENDENCY_TEMPLATE)?;
         str_templates.add_template("enum", ENUM_TEMPLATE)?;
+        str_templates.add_template("legend", LEGEND_TEMPLATE)?;
         str_templates.set_default_formatter(&format_unescaped);
         Ok(MermaidGenerator { str_templates })
     }
 
-    fn entity_render(
+    // Returns rendered (PK columns, FK columns, other columns)
+    fn columns_render(
         &self,
-        tbl: &Table,
+        entity_name: &str,
+        columns: &[Arc<TableColumn>],
         opts: &GeneratorConfigOptions,
-    ) -> Result<String, crate::SqlantError> {
+    ) -> Result<(String, String, String), crate::SqlantError> {
         enum RenderType {
             PK,     // only pk columns
             FK,     // only pure FK columns (Non PK)
             Others, // non pk and non fk
         }
         let columns_render = |rt: RenderType| {
-            tbl.columns
+            columns
                 .iter()
                 .filter(|col| match rt {
                     RenderType::PK => col.is_pk(),
@@ -112,11 +164,14 @@ impl<'a> MermaidGenerator<'a> {
                     RenderType::Others => !col.is_pk() && !col.is_fk(),
                 })
                 .try_fold(String::new(), |acc, col| {
+                    // PK is unique anyway
+                    let is_uq = col.is_unique() && !col.is_pk();
                     let column = &SColumn {
                         col: col.as_ref(),
                         is_fk: col.is_fk(),
                         is_pk: col.is_pk(),
-                        is_pk_or_fk: col.is_pk() || col.is_fk(),
+                        is_uq,
+                        is_key: col.is_pk() || col.is_fk() || is_uq,
                         is_nn: opts.not_null && col.is_nn(),
                     };
                     let mut res: String = self
@@ -124,32 +179,297 @@ impl<'a> MermaidGenerator<'a> {
                         .render("column", &column)?
                         .trim_end_matches([','])
                         .into();
-                    if column.is_nn {
-                        res += " \"NN\"";
+                    let mut comment = Self::column_comment(col, column.is_nn, opts.draw_comments);
+                    // Columns can't be styled, so change is written into comment
+                    if let Some(change) = opts
+                        .diff
+                        .as_ref()
+                        .and_then(|diff| diff.column_change(entity_name, &col.name))
+                    {
+                        comment = [change.as_str(), &comment]
+                            .iter()
+                            .filter(|s| !s.is_empty())
+                            .copied()
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                    }
+                    if !comment.is_empty() {
+                        res += &format!(" \"{comment}\"");
                     }
 
                     Ok::<std::string::String, crate::SqlantError>(acc + &res + "\n")
                 })
         };
+        Ok((
+            columns_render(RenderType::PK)?,
+            columns_render(RenderType::FK)?,
+            columns_render(RenderType::Others)?,
+        ))
+    }
+
+    fn entity_render(
+        &self,
+        tbl: &Table,
+        opts: &GeneratorConfigOptions,
+    ) -> Result<String, crate::SqlantError> {
+        let (pks, fks, others) = if opts.conceptual_diagram {
+            Default::default()
+        } else {
+            self.columns_render(&tbl.name, &tbl.columns, opts)?
+        };
+        Ok(self.str_templates.render(
+            "ent",
+            &SEntity {
+                pks,
+                fks,
+                others,
+                name: Self::entity_name(&tbl.name),
+                change: opts
+                    .diff
+                    .as_ref()
+                    .and_then(|diff| diff.table_change(&tbl.name))
+                    .map(|change| change.as_str()),
+                comment: Self::comment(tbl.comment.as_deref(), opts),
+                indexes: if opts.draw_indexes && !opts.conceptual_diagram {
+                    tbl.indexes.iter().map(ToString::to_string).collect()
+                } else {
+                    vec![]
+                },
+            },
+        )?)
+    }
+
+    // Columns of views have no keys, so all of them are rendered as others
+    fn view_render(
+        &self,
+        view: &View,
+        opts: &GeneratorConfigOptions,
+    ) -> Result<String, crate::SqlantError> {
+        let others = if opts.conceptual_diagram {
+            String::new()
+        } else {
+            self.columns_render(&view.name, &view.columns, opts)?.2
+        };
         Ok(self.str_templates.render(
             "ent",
             &SEntity {
-                pks: columns_render(RenderType::PK)?,
-                fks: columns_render(RenderType::FK)?,
-                others: columns_render(RenderType::Others)?,
-                name: tbl.name.clone(),
+                pks: String::new(),
+                fks: String::new(),
+                others,
+                name: Self::view_name(view),
+                change: opts
+                    .diff
+                    .as_ref()
+                    .and_then(|diff| diff.view_change(&view.name))
+                    .map(|change| change.as_str()),
+                comment: Self::comment(view.comment.as_deref(), opts),
+                indexes: vec![],
             },
         )?)
     }
 
+    fn comment(comment: Option<&str>, opts: &GeneratorConfigOptions) -> Option<String> {
+        comment
+            .filter(|_| opts.draw_comments)
+            .map(|comment| comment.replace('\n', " "))
+    }
+
+    // Mermaid allows only one comment per attribute
+    fn column_comment(col: &TableColumn, is_nn: bool, draw_comments: bool) -> String {
+        let mut parts = vec![];
+        if let Some(comment) = col.comment.as_ref().filter(|_| draw_comments) {
+            parts.push(comment.replace('\n', " "));
+        }
+        if is_nn {
+            parts.push("NN".to_string());
+        }
+        if let Some(default) = col.default_value() {
+            parts.push(format!("default: {default}"));
+        }
+        for check in col.checks() {
+            parts.push(format!("check: {check}"));
+        }
+        // Double quotes can't be escaped inside of comment
+        parts.join(", ").replace('"', "")
+    }
+
+    // Qualified names (schema.table) must be quoted
+    fn entity_name(name: &str) -> String {
+        if name.contains('.') {
+            format!("\"{name}\"")
+        } else {
+            name.to_string()
+        }
+    }
+
+    // Tables whose PK consists of columns of two FKs are rendered as many-to-many
+    // relationship in conceptual diagram. Ex: student_course(student_id, course_id).
+    // Tables that are referenced by FKs or views or are focused are kept.
+    // key - junction table name, value - its FKs
+    fn junction_tables<'b>(
+        sql_erd: &'b SqlERData,
+        opts: &GeneratorConfigOptions,
+    ) -> BTreeMap<&'b str, [&'b ForeignKey; 2]> {
+        let mut res = BTreeMap::new();
+        if !opts.conceptual_diagram {
+            return res;
+        }
+        for tbl in &sql_erd.tables {
+            let fks: Vec<&ForeignKey> = sql_erd
+                .foreign_keys
+                .iter()
+                .filter(|fk| fk.source_table.name == tbl.name)
+                .collect();
+            let [first, second] = fks[..] else {
+                continue;
+            };
+            let pks: Vec<&Arc<TableColumn>> = tbl.columns.iter().filter(|c| c.is_pk()).collect();
+            let is_fk_column = |col: &TableColumn| {
+                [first, second]
+                    .iter()
+                    .any(|fk| fk.source_columns.iter().any(|c| c.name == col.name))
+            };
+            let is_referenced = sql_erd
+                .foreign_keys
+                .iter()
+                .any(|fk| fk.target_table.name == tbl.name)
+                || sql_erd
+                    .views
+                    .iter()
+                    .any(|view| view.depends_on.contains(&tbl.name))
+                || opts.focus_tables.contains(&tbl.name);
+            if !pks.is_empty() && pks.iter().all(|col| is_fk_column(col)) && !is_referenced {
+                res.insert(tbl.name.as_str(), [first, second]);
+            }
+        }
+        res
+    }
+
+    // Only markers that are present in diagram are explained
+    fn legend_render(
+        &self,
+        sql_erd: &SqlERData,
+        opts: &GeneratorConfigOptions,
+        has_junction_tables: bool,
+        has_dependencies: bool,
+    ) -> Result<Option<String>, crate::SqlantError> {
+        let columns: Vec<&Arc<TableColumn>> = if opts.conceptual_diagram {
+            vec![]
+        } else {
+            sql_erd.tables.iter().flat_map(|tbl| &tbl.columns).collect()
+        };
+        let has_views =
+            |materialized: bool| sql_erd.views.iter().any(|v| v.materialized == materialized);
+        let rows: Vec<SLegendRow> = [
+            (columns.iter().any(|c| c.is_pk()), Some("PK"), "Primary Key"),
+            (columns.iter().any(|c| c.is_fk()), Some("FK"), "Foreign Key"),
+            (
+                columns.iter().any(|c| c.is_unique() && !c.is_pk()),
+                Some("UK"),
+                "Unique",
+            ),
+            (
+                opts.not_null && columns.iter().any(|c| c.is_nn()),
+                None,
+                "NN: Mandatory field (Not Null)",
+            ),
+            (
+                opts.draw_enums && !sql_erd.enums.is_empty(),
+                None,
+                "(ENUM): Enum",
+            ),
+            (has_views(false), None, "(V): View"),
+            (has_views(true), None, "(MV): Materialized View"),
+            (
+                has_junction_tables,
+                None,
+                "}o--o{: Many-to-many relationship (junction table)",
+            ),
+            (
+                has_dependencies,
+                None,
+                "}o..o{: View reads from table or view",
+            ),
+        ]
+        .into_iter()
+        .filter(|(is_present, _, _)| *is_present)
+        .map(|(_, key, description)| SLegendRow { key, description })
+        .collect();
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.str_templates.render("legend", &SLegend { rows })?,
+        ))
+    }
+
+    // Double quotes can't be escaped inside of label
+    fn label(parts: Vec<String>, change: Option<Change>) -> String {
+        parts
+            .into_iter()
+            .chain(change.map(|change| change.as_str().to_string()))
+            .collect::<Vec<_>>()
+            .join(", ")
+            .replace('"', "")
+    }
+
+    // Views are distinguished from tables by name suffix. Ex: "top_customers (V)"
+    fn view_name(view: &View) -> String {
+        let suffix = if view.materialized { "MV" } else { "V" };
+        format!("\"{} ({suffix})\"", view.name)
+    }
+
+    // Dependencies on entities which aren't drawn are skipped
+    fn dependencies_render(
+        &self,
+        sql_erd: &SqlERData,
+        opts: &GeneratorConfigOptions,
+    ) -> Result<Vec<String>, crate::SqlantError> {
+        sql_erd
+            .view_dependencies()
+            .into_iter()
+            .map(|(view, name)| {
+                let source_name = match sql_erd.views.iter().find(|v| v.name == name) {
+                    Some(source) => Self::view_name(source),
+                    None => Self::entity_name(name),
+                };
+                self.str_templates.render(
+                    "dependency",
+                    &SDependency {
+                        view_name: Self::view_name(view),
+                        source_name,
+                        columns: view
+                            .used_columns
+                            .get(name)
+                            .filter(|_| opts.draw_lineage)
+                            .map(|columns| columns.join(", ")),
+                    },
+                )
+            })
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(Into::into)
+    }
+
+    // Attribute type is a single word without dots.
+    // Ex: timestamp with time zone -> timestamp_with_time_zone, billing.status -> billing_status
+    fn datatype(datatype: &str) -> String {
+        datatype.replace([' ', '.'], "_")
+    }
+
     // Preprocess sql_erd data to make it compatible with mermaid ERD
     fn preprocess(sql_erd: &mut SqlERData) {
         for table in sql_erd.tables.iter_mut() {
             let tbl = Arc::make_mut(table);
             for c in &mut tbl.columns {
                 let c = Arc::make_mut(c);
-                let replaced_string = c.datatype.replace(' ', "_");
-                c.datatype = replaced_string;
+                c.datatype = Self::datatype(&c.datatype);
+            }
+        }
+        for view in sql_erd.views.iter_mut() {
+            let view = Arc::make_mut(view);
+            for c in &mut view.columns {
+                let c = Arc::make_mut(c);
+                c.datatype = Self::datatype(&c.datatype);
             }
         }
     }
@@ -162,23 +482,52 @@ impl ViewGenerator for MermaidGenerator<'_> {
         opts: &GeneratorConfigOptions,
     ) -> Result<String, crate::SqlantError> {
         Self::preprocess(&mut sql_erd);
+        let junction_tables = Self::junction_tables(&sql_erd, opts);
         let entities: Vec<String> = sql_erd
             .tables
             .iter()
+            .filter(|tbl| !junction_tables.contains_key(tbl.name.as_str()))
             .map(|tbl| self.entity_render(tbl, opts))
             .collect::<Result<Vec<String>, crate::SqlantError>>()?;
+        let views: Vec<String> = sql_erd
+            .views
+            .iter()
+            .map(|view| self.view_render(view, opts))
+            .collect::<Result<Vec<String>, crate::SqlantError>>()?;
+        let dependencies = self.dependencies_render(&sql_erd, opts)?;
         let foreign_keys: Vec<String> = sql_erd
             .foreign_keys
             .iter()
-            .map(|fk| {
-                self.str_templates.render(
-                    "rel",
-                    &SForeignKey {
-                        source_table_name: fk.source_table.name.clone(),
-                        target_table_name: fk.target_table.name.clone(),
+            .filter_map(|fk| {
+                let rel = match junction_tables.get(fk.source_table.name.as_str()) {
+                    // Relationship is rendered once for both FKs of junction table
+                    Some([first, _]) if !std::ptr::eq(*first, fk) => return None,
+                    Some([first, second]) => SForeignKey {
+                        source_table_name: Self::entity_name(&first.target_table.name),
+                        target_table_name: Self::entity_name(&second.target_table.name),
+                        is_zero_one_to_one: false,
+                        is_many_to_many: true,
+                        label: Self::label(
+                            vec![fk.source_table.name.clone()],
+                            opts.diff
+                                .as_ref()
+                                .and_then(|diff| diff.table_change(&fk.source_table.name)),
+                        ),
+                    },
+                    None => SForeignKey {
+                        source_table_name: Self::entity_name(&fk.source_table.name),
+                        target_table_name: Self::entity_name(&fk.target_table.name),
                         is_zero_one_to_one: fk.is_zero_one_to_one,
+                        is_many_to_many: false,
+                        label: Self::label(
+                            fk.name.iter().cloned().chain(fk.clauses()).collect(),
+                            opts.diff
+                                .as_ref()
+                                .and_then(|diff| diff.foreign_key_change(fk)),
+                        ),
                     },
-                )
+                };
+                Some(self.str_templates.render("rel", &rel))
             })
             .collect::<Result<Vec<String>, _>>()?;
 
@@ -192,6 +541,11 @@ impl ViewGenerator for MermaidGenerator<'_> {
                         &SEnum {
                             name: name.to_string(),
                             values: values.to_vec(),
+                            change: opts
+                                .diff
+                                .as_ref()
+                                .and_then(|diff| diff.enum_change(name))
+                                .map(|change| change.as_str()),
                         },
                     )
                 })
@@ -200,13 +554,35 @@ impl ViewGenerator for MermaidGenerator<'_> {
             vec![]
         };
 
+        let legend = if opts.draw_legend {
+            self.legend_render(
+                &sql_erd,
+                opts,
+                !junction_tables.is_empty(),
+                !dependencies.is_empty(),
+            )?
+        } else {
+            None
+        };
+
         Ok(self.str_templates.render(
             "mermaid",
             &SMermaid {
                 direction: opts.direction.as_ref().map(Into::into),
+                diff: opts.diff.is_some(),
+                focus_tables: (!opts.focus_tables.is_empty()).then(|| {
+                    opts.focus_tables
+                        .iter()
+                        .map(|name| Self::entity_name(name))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                }),
                 entities,
+                views,
                 enums,
                 foreign_keys,
+                dependencies,
+                legend,
             },
         )?)
     }
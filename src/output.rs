@@ -0,0 +1,31 @@
+use std::fs;
+use std::path::Path;
+
+use crate::SqlantError;
+
+/// Writes `content` into temporary file next to `path` and renames it to `path`,
+/// so readers never see partially written diagram. Parent directories are created.
+pub fn write_file(path: &Path, content: &str) -> Result<(), SqlantError> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir).map_err(SqlantError::io(dir))?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| {
+            SqlantError::io(path)(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "not a file path",
+            ))
+        })?
+        .to_string_lossy();
+    let tmp_path = dir.join(format!(".{file_name}.{}.tmp", std::process::id()));
+    let res = fs::write(&tmp_path, content)
+        .map_err(SqlantError::io(&tmp_path))
+        .and_then(|_| fs::rename(&tmp_path, path).map_err(SqlantError::io(path)));
+    if res.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    res
+}
@@ -0,0 +1,257 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::output::write_file;
+use crate::sql_entities::{SqlERData, Table, TableColumn};
+use crate::table_filter::TableFilter;
+use crate::{GeneratorConfigOptions, SqlantError, ViewGenerator};
+
+/// Name of partition with tables without foreign keys (or not matching any group) and views
+pub static OTHERS_PARTITION: &str = "others";
+
+/// Part of schema that is rendered as a separate diagram
+#[derive(Clone, Debug)]
+pub struct Partition {
+    pub name: String,
+    pub sql_erd: SqlERData,
+}
+
+/// Named groups of tables. Ex of groups file:
+/// `{"orders": ["customer_order", "order_*"], "catalog": ["product", "sku", "vendor*"]}`
+/// Patterns are the same as in TableFilter include patterns.
+pub type PartitionGroups = BTreeMap<String, TableFilter>;
+
+pub fn load_groups(path: &str) -> Result<PartitionGroups, SqlantError> {
+    let json = fs::read_to_string(path).map_err(SqlantError::io(Path::new(path)))?;
+    let groups: BTreeMap<String, Vec<String>> = serde_json::from_str(&json)?;
+    groups
+        .into_iter()
+        .map(|(name, patterns)| {
+            let no_patterns: &[String] = &[];
+            Ok((name, TableFilter::new(&patterns, no_patterns)?))
+        })
+        .collect()
+}
+
+// Table names are qualified as `schema.table` if several schemas are loaded
+fn short_name(tbl: &Table) -> &str {
+    tbl.schema
+        .as_deref()
+        .and_then(|schema| tbl.name.strip_prefix(&format!("{schema}.")))
+        .unwrap_or(&tbl.name)
+}
+
+// Subset of `sql_erd` with `table_names`, FKs between them and enums used by them
+fn sub_erd(sql_erd: &SqlERData, table_names: &BTreeSet<&str>, with_views: bool) -> SqlERData {
+    let tables: Vec<Arc<Table>> = sql_erd
+        .tables
+        .iter()
+        .filter(|tbl| table_names.contains(tbl.name.as_str()))
+        .cloned()
+        .collect();
+    let views = if with_views {
+        sql_erd.views.clone()
+    } else {
+        vec![]
+    };
+    let datatypes: BTreeSet<&str> = tables
+        .iter()
+        .flat_map(|tbl| &tbl.columns)
+        .chain(views.iter().flat_map(|view| &view.columns))
+        .map(|col: &Arc<TableColumn>| col.datatype.as_str())
+        .collect();
+    SqlERData {
+        foreign_keys: sql_erd
+            .foreign_keys
+            .iter()
+            .filter(|fk| {
+                table_names.contains(fk.source_table.name.as_str())
+                    && table_names.contains(fk.target_table.name.as_str())
+            })
+            .cloned()
+            .collect(),
+        enums: sql_erd
+            .enums
+            .iter()
+            .filter(|(name, _)| datatypes.contains(name.as_str()))
+            .map(|(name, values)| (name.clone(), values.clone()))
+            .collect(),
+        tables,
+        views,
+    }
+}
+
+fn others_partition(sql_erd: &SqlERData, table_names: BTreeSet<&str>) -> Option<Partition> {
+    (!table_names.is_empty() || !sql_erd.views.is_empty()).then(|| Partition {
+        name: OTHERS_PARTITION.to_string(),
+        sql_erd: sub_erd(sql_erd, &table_names, true),
+    })
+}
+
+/// Splits schema into connected components of foreign keys graph.
+/// Component is named by its table with the most foreign keys.
+/// Tables without foreign keys and views are put into `others` partition.
+pub fn split_by_components(sql_erd: &SqlERData) -> Vec<Partition> {
+    let mut neighbours: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for fk in &sql_erd.foreign_keys {
+        let (source, target) = (fk.source_table.name.as_str(), fk.target_table.name.as_str());
+        neighbours.entry(source).or_default().insert(target);
+        neighbours.entry(target).or_default().insert(source);
+    }
+    let fks_count = |name: &str| {
+        sql_erd
+            .foreign_keys
+            .iter()
+            .filter(|fk| fk.source_table.name == name || fk.target_table.name == name)
+            .count()
+    };
+
+    let mut res = vec![];
+    let mut visited: BTreeSet<&str> = BTreeSet::new();
+    let mut others = BTreeSet::new();
+    for tbl in &sql_erd.tables {
+        let name = tbl.name.as_str();
+        if visited.contains(name) {
+            continue;
+        }
+        if !neighbours.contains_key(name) {
+            others.insert(name);
+            continue;
+        }
+        // Depth-first search of component
+        let mut component = BTreeSet::new();
+        let mut stack = vec![name];
+        while let Some(name) = stack.pop() {
+            if visited.insert(name) {
+                component.insert(name);
+                stack.extend(neighbours.get(name).into_iter().flatten());
+            }
+        }
+        let hub = component
+            .iter()
+            .max_by_key(|&&name| (fks_count(name), std::cmp::Reverse(name)))
+            .copied()
+            .unwrap_or(name);
+        // Qualified name is kept if tables of several schemas have the same name
+        let name = sql_erd
+            .tables
+            .iter()
+            .find(|tbl| tbl.name == hub)
+            .map_or(hub, |tbl| {
+                let name = short_name(tbl);
+                let same_name_count = sql_erd
+                    .tables
+                    .iter()
+                    .filter(|tbl| short_name(tbl) == name)
+                    .count();
+                if same_name_count > 1 {
+                    hub
+                } else {
+                    name
+                }
+            });
+        res.push(Partition {
+            name: name.to_string(),
+            sql_erd: sub_erd(sql_erd, &component, false),
+        });
+    }
+    res.extend(others_partition(sql_erd, others));
+    res
+}
+
+/// Splits schema into user-defined groups. Table can be in several groups.
+/// Tables not matching any group and views are put into `others` partition.
+pub fn split_by_groups(sql_erd: &SqlERData, groups: &PartitionGroups) -> Vec<Partition> {
+    let mut grouped = BTreeSet::new();
+    let mut res = vec![];
+    for (name, table_filter) in groups {
+        let table_names: BTreeSet<&str> = sql_erd
+            .tables
+            .iter()
+            .filter(|tbl| table_filter.is_match(tbl.schema.as_deref(), short_name(tbl)))
+            .map(|tbl| tbl.name.as_str())
+            .collect();
+        grouped.extend(table_names.iter().copied());
+        res.push(Partition {
+            name: name.clone(),
+            sql_erd: sub_erd(sql_erd, &table_names, false),
+        });
+    }
+    let others = sql_erd
+        .tables
+        .iter()
+        .map(|tbl| tbl.name.as_str())
+        .filter(|name| !grouped.contains(name))
+        .collect();
+    res.extend(others_partition(sql_erd, others));
+    res
+}
+
+// Partition name can contain any characters (e.g. schema.table)
+fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+// Partitions can have the same file stem (e.g. table or group named `others`),
+// so `_2`, `_3`, ... is appended to the repeated ones
+fn unique_file_stem(name: &str, used_stems: &mut BTreeSet<String>) -> String {
+    let stem = file_stem(name);
+    let mut res = stem.clone();
+    let mut n = 1;
+    while !used_stems.insert(res.clone()) {
+        n += 1;
+        res = format!("{stem}_{n}");
+    }
+    res
+}
+
+/// Renders every partition into `<out_dir>/<partition>.<extension>` and writes
+/// `<out_dir>/index.md` with links to them
+pub fn write_partitions(
+    partitions: Vec<Partition>,
+    generator: &dyn ViewGenerator,
+    opts: &GeneratorConfigOptions,
+    out_dir: &Path,
+    extension: &str,
+) -> Result<(), SqlantError> {
+    let mut index = String::from("# ER diagrams\n\n");
+    let mut used_stems = BTreeSet::new();
+    for partition in partitions {
+        let file_name = format!(
+            "{}.{extension}",
+            unique_file_stem(&partition.name, &mut used_stems)
+        );
+        let tables: Vec<&str> = partition
+            .sql_erd
+            .tables
+            .iter()
+            .map(|tbl| tbl.name.as_str())
+            .chain(
+                partition
+                    .sql_erd
+                    .views
+                    .iter()
+                    .map(|view| view.name.as_str()),
+            )
+            .collect();
+        index += &format!(
+            "- [{}]({file_name}): {}\n",
+            partition.name,
+            tables.join(", ")
+        );
+        let result = generator.generate(partition.sql_erd, opts)?;
+        write_file(&out_dir.join(&file_name), &(result + "\n"))?;
+    }
+    write_file(&out_dir.join("index.md"), &index)?;
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
-use super::sql_entities::{SqlERData, Table, TableColumn};
-use crate::{sql_entities::View, Direction, GeneratorConfigOptions, ViewGenerator};
+use super::sql_entities::{ReferentialAction, SqlERData, Table, TableColumn, TableIndex};
+use crate::{
+    diff::Change, sql_entities::View, Direction, GeneratorConfigOptions, GeneratorType,
+    ViewGenerator,
+};
 use serde::Serialize;
 use tinytemplate::{format_unescaped, TinyTemplate};
 
@@ -13,25 +16,42 @@ static PUML_TEMPLATE: &str = "@startuml\n\n\
 {{ if direction }}{ direction }\n{{ endif }}\
     hide circle\n\
     hide empty members\n\
-    skinparam linetype ortho\n\n\
+    skinparam linetype ortho\n\
+{{ if packages }}set namespaceSeparator none\n{{ endif }}\n\
     {puml_lib}\n\n\
+    {{ for package in packages}}{package}\n{{ endfor }}\
     {{ for ent in entities}}{ent}\n{{ endfor }}\n\
     {{ for view in views}}{view}\n{{ endfor }}\n\
     {{ for fk in foreign_keys}}{fk}\n{{ endfor }}\n\
+    {{ for dep in dependencies}}{dep}\n{{ endfor }}{{ if dependencies }}\n{{ endif }}\
     {{ for e in enums}}{e}\n{{ endfor }}{legend}\n@enduml";
 
 static ENTITY_TEMPLATE: &str =
-    "table({name}) \\{\n{pks} {{ if pks }} ---\n{{ endif }}{fks}{nns}{others}}\n";
+    "table({name}){{ if color }} {color}{{ endif }} \\{\n{pks} {{ if pks }} ---\n{{ endif }}{fks}{nns}{others}\
+    {{ if indexes }}  .. indexes ..\n{{ for idx in indexes }}{idx}{{ endfor }}{{ endif }}}\n\
+    {{ if comments }}note right of {name}\n{comments}end note\n{{ endif }}";
+
+static INDEX_TEMPLATE: &str = "  index({idx.name}, \"{columns}\"{{ if idx.unique }}, $unique=true{{ endif }}, $method=\"{idx.method}\"{{ if include }}, $include=\"{include}\"{{ endif }}{{ if predicate }}, $predicate=\"{predicate}\"{{ endif }})\n";
 
 static VIEW_TEMPLATE: &str =
-    "view({name}{{ if materialized}}, $materialized=true{{ endif }}) \\{\n{columns}}\n";
+    "view({name}{{ if materialized}}, $materialized=true{{ endif }}){{ if color }} {color}{{ endif }} \\{\n{columns}}\n\
+    {{ if comments }}note right of {name}\n{comments}end note\n{{ endif }}";
+
+static PACKAGE_TEMPLATE: &str = "package {name} \\{\n\
+    {{ for ent in entities}}{ent}\n{{ endfor }}\
+    {{ for view in views}}{view}\n{{ endfor }}}\n";
+
+static COLUMN_TEMPLATE: &str = "  column({col.name}, \"{col.datatype}\"{{ if is_pk }}, $pk=true{{ endif }}{{ if is_fk }}, $fk=true{{ endif }}{{if is_nn}}, $nn=true{{ endif }}{{ if is_uq }}, $uq=true{{ endif }}{{ if default }}, $default=\"{default}\"{{ endif }}{{ if check }}, $check=\"{check}\"{{ endif }}{{ if diff }}, $diff=\"{diff}\"{{ endif }})\n";
 
-static COLUMN_TEMPLATE: &str = "  column({col.name}, \"{col.datatype}\"{{ if is_pk }}, $pk=true{{ endif }}{{ if is_fk }}, $fk=true{{ endif }}{{if is_nn}}, $nn=true{{ endif }})\n";
+// Label is name of constraint and its non-default clauses. Ex: ON DELETE CASCADE
+static REL_TEMPLATE: &str = "{source_table_name} {{ if is_zero_one_to_one }}|o{{else}}}o{{ endif }}-{{ if color }}[{color}]{{ endif }}-|| {target_table_name}{{ if label }} : {label}{{ endif }}\n";
 
-static REL_TEMPLATE: &str = "{source_table_name} {{ if is_zero_one_to_one }}|o--||{{else}}}o--||{{ endif }} {target_table_name}\n";
+// View depends on table or view. Label is list of used columns if lineage is drawn
+static DEPENDENCY_TEMPLATE: &str =
+    "{view_name} ..> {source_name}{{ if columns }} : {columns}{{ endif }}\n";
 
 static ENUM_TEMPLATE: &str =
-    "enum({name}, \"{{ for v in values}}{{if @last}}{v}{{else}}{v}, {{ endif }}{{ endfor }}\")\n";
+    "enum({name}, \"{{ for v in values}}{{if @last}}{v}{{else}}{v}, {{ endif }}{{ endfor }}\"{{ if color }}, $color=\"{color}\"{{ endif }})\n";
 
 static PUML_LEGEND: &str = r#"add_legend()"#;
 
@@ -39,6 +59,7 @@ static PUML_LEGEND: &str = r#"add_legend()"#;
 struct SSqlEnum {
     name: String,
     values: Vec<String>,
+    color: Option<&'static str>,
 }
 
 #[derive(Serialize)]
@@ -48,6 +69,70 @@ struct SColumn<'a> {
     is_pk: bool,
     is_nn: bool,
     is_nn_and_not_pk: bool,
+    is_uq: bool,
+    default: Option<String>,
+    check: Option<String>,
+    diff: Option<&'static str>, // added, removed or changed
+}
+
+#[derive(Serialize)]
+struct SIndex<'a> {
+    idx: &'a TableIndex,
+    columns: String,
+    include: String,
+    predicate: Option<String>,
+}
+
+// Colors of objects in diff diagram
+fn background_color(change: Change) -> &'static str {
+    match change {
+        Change::Added => "#C8F7C5",
+        Change::Removed => "#F7C5C5",
+        Change::Changed => "#FFE0A3",
+    }
+}
+
+fn line_color(change: Change) -> &'static str {
+    match change {
+        Change::Added => "#2E8B57",
+        Change::Removed => "#D9534F",
+        Change::Changed => "#E69500",
+    }
+}
+
+// Color of foreign keys with ON DELETE CASCADE
+pub(crate) static CASCADE_COLOR: &str = "#C0392B";
+
+// Root tables of neighbourhood diagram are drawn with bold border
+fn entity_color(background: Option<&str>, is_focused: bool) -> Option<String> {
+    match (background, is_focused) {
+        (Some(background), true) => Some(format!("{background};line.bold;line:DarkOrange")),
+        (None, true) => Some("#line.bold;line:DarkOrange".to_string()),
+        (background, false) => background.map(ToString::to_string),
+    }
+}
+
+// Double quotes can't be escaped inside of PlantUML function arguments
+fn escape(s: &str) -> String {
+    s.replace('"', "")
+}
+
+impl<'a> SColumn<'a> {
+    fn new(col: &'a TableColumn) -> SColumn<'a> {
+        let checks = col.checks();
+        SColumn {
+            col,
+            is_fk: col.is_fk(),
+            is_pk: col.is_pk(),
+            is_nn: col.is_nn(),
+            is_nn_and_not_pk: col.is_nn() && (!col.is_pk()),
+            // PK is unique anyway
+            is_uq: col.is_unique() && !col.is_pk(),
+            default: col.default_value().map(escape),
+            check: (!checks.is_empty()).then(|| escape(&checks.join(" AND "))),
+            diff: None,
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -55,6 +140,15 @@ struct SView {
     name: String,
     columns: String,
     materialized: bool,
+    comments: Option<String>,
+    color: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct SPackage<'a> {
+    name: &'a str,
+    entities: Vec<String>,
+    views: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -64,6 +158,9 @@ struct SEntity {
     fks: String,    // Columns that contain FK and don't contain PK
     nns: String,    // NOT NULL Columns that don't contain both PK and FK
     others: String, // Columns that don't contain both PK and FK
+    indexes: Vec<String>,
+    comments: Option<String>, // Comments of table and its columns
+    color: Option<String>,    // Ex: #FFE0A3;line.bold;line:DarkOrange
 }
 
 #[derive(Serialize)]
@@ -77,13 +174,11 @@ impl TryFrom<&Direction> for SDirection {
     fn try_from(value: &Direction) -> Result<Self, crate::SqlantError> {
         match value {
             Direction::TB => Ok(Self("top to bottom direction".into())),
-            Direction::BT => Err(crate::SqlantError::Generator(
-                "bt (bottom-to-top) direction is not available in plantuml.".into(),
-            )),
             Direction::LR => Ok(Self("left to right direction".into())),
-            Direction::RL => Err(crate::SqlantError::Generator(
-                "rl (right-to-left) direction is not available in plantuml.".into(),
-            )),
+            Direction::BT | Direction::RL => Err(crate::SqlantError::UnsupportedDirection {
+                generator: GeneratorType::PlantUML,
+                direction: value.clone(),
+            }),
         }
     }
 }
@@ -91,27 +186,39 @@ impl TryFrom<&Direction> for SDirection {
 #[derive(Serialize)]
 struct SPuml {
     puml_lib: String,
+    // Tables and views grouped by schema if several schemas are loaded
+    packages: Vec<String>,
     // entities can be renamed to "tables"
     entities: Vec<String>,
     views: Vec<String>,
     foreign_keys: Vec<String>,
+    dependencies: Vec<String>,
     enums: Vec<String>,
     legend: Option<SLegend>,
     direction: Option<SDirection>,
 }
 
+#[derive(Serialize)]
+struct SDependency<'a> {
+    view_name: &'a str,
+    source_name: &'a str,
+    columns: Option<String>,
+}
+
 #[derive(Serialize)]
 struct SForeignKey {
     source_table_name: String,
     target_table_name: String,
     is_zero_one_to_one: bool,
+    color: Option<&'static str>,
+    label: String, // Lines are separated by \n
 }
 
-struct SortedColumns {
-    pks: Vec<Arc<TableColumn>>,
-    fks: Vec<Arc<TableColumn>>,
-    nns: Vec<Arc<TableColumn>>,
-    others: Vec<Arc<TableColumn>>,
+pub(crate) struct SortedColumns {
+    pub(crate) pks: Vec<Arc<TableColumn>>,
+    pub(crate) fks: Vec<Arc<TableColumn>>,
+    pub(crate) nns: Vec<Arc<TableColumn>>,
+    pub(crate) others: Vec<Arc<TableColumn>>,
 }
 
 impl<'a> PlantUmlDefaultGenerator<'a> {
@@ -121,7 +228,10 @@ impl<'a> PlantUmlDefaultGenerator<'a> {
         str_templates.add_template("column", COLUMN_TEMPLATE)?;
         str_templates.add_template("ent", ENTITY_TEMPLATE)?;
         str_templates.add_template("view", VIEW_TEMPLATE)?;
+        str_templates.add_template("index", INDEX_TEMPLATE)?;
+        str_templates.add_template("package", PACKAGE_TEMPLATE)?;
         str_templates.add_template("rel", REL_TEMPLATE)?;
+        str_templates.add_template("dependency", DEPENDENCY_TEMPLATE)?;
         str_templates.add_template("enum", ENUM_TEMPLATE)?;
         str_templates.add_template("legend", PUML_LEGEND)?;
         str_templates.set_default_formatter(&format_unescaped);
@@ -133,7 +243,7 @@ impl<'a> PlantUmlDefaultGenerator<'a> {
     // 2. FKs
     // 3. NN
     // 4. Others
-    fn sort_columns(cols: &[Arc<TableColumn>]) -> SortedColumns {
+    pub(crate) fn sort_columns(cols: &[Arc<TableColumn>]) -> SortedColumns {
         let mut cloned_cols = cols.to_owned();
         let mut pks = Vec::new();
         let mut fks = Vec::new();
@@ -176,7 +286,12 @@ impl<'a> PlantUmlDefaultGenerator<'a> {
             others,
         }
     }
-    fn entity_render(&self, tbl: &Table, conceptual: bool) -> Result<String, crate::SqlantError> {
+    fn entity_render(
+        &self,
+        tbl: &Table,
+        opts: &GeneratorConfigOptions,
+    ) -> Result<String, crate::SqlantError> {
+        let conceptual = opts.conceptual_diagram;
         let sorted_columns = Self::sort_columns(&tbl.columns);
 
         let columns_render = |columns: Vec<Arc<TableColumn>>| -> Result<String, _> {
@@ -186,11 +301,12 @@ impl<'a> PlantUmlDefaultGenerator<'a> {
                     let r = self.str_templates.render(
                         "column",
                         &SColumn {
-                            col: col.as_ref(),
-                            is_fk: col.is_fk(),
-                            is_pk: col.is_pk(),
-                            is_nn: col.is_nn(),
-                            is_nn_and_not_pk: col.is_nn() && (!col.is_pk()),
+                            diff: opts
+                                .diff
+                                .as_ref()
+                                .and_then(|diff| diff.column_change(&tbl.name, &col.name))
+                                .map(|change| change.as_str()),
+                            ..SColumn::new(col.as_ref())
                         },
                     );
                     match r {
@@ -217,11 +333,73 @@ impl<'a> PlantUmlDefaultGenerator<'a> {
                 nns: columns_render_if_not_conceptual(sorted_columns.nns)?,
                 others: columns_render_if_not_conceptual(sorted_columns.others)?,
                 name: tbl.name.clone(),
+                color: entity_color(
+                    opts.diff
+                        .as_ref()
+                        .and_then(|diff| diff.table_change(&tbl.name))
+                        .map(background_color),
+                    opts.focus_tables.contains(&tbl.name),
+                ),
+                comments: if opts.draw_comments {
+                    Self::note(tbl.comment.as_deref(), &tbl.columns, conceptual)
+                } else {
+                    None
+                },
+                indexes: if opts.draw_indexes && !conceptual {
+                    tbl.indexes
+                        .iter()
+                        .map(|idx| {
+                            self.str_templates.render(
+                                "index",
+                                &SIndex {
+                                    idx,
+                                    columns: escape(&idx.columns.join(", ")),
+                                    include: escape(&idx.include.join(", ")),
+                                    predicate: idx.predicate.as_deref().map(escape),
+                                },
+                            )
+                        })
+                        .collect::<Result<Vec<String>, _>>()?
+                } else {
+                    vec![]
+                },
             },
         )?)
     }
 
-    fn view_render(&self, view: &View, conceptual: bool) -> Result<String, crate::SqlantError> {
+    // Comment of entity and comments of its columns
+    fn note(
+        comment: Option<&str>,
+        columns: &[Arc<TableColumn>],
+        conceptual: bool,
+    ) -> Option<String> {
+        let mut res = String::new();
+        if let Some(comment) = comment {
+            res += &format!("  {}\n", comment.replace('\n', "\n  "));
+        }
+        if !conceptual {
+            let columns: Vec<String> = columns
+                .iter()
+                .filter_map(|col| {
+                    col.comment.as_ref().map(|comment| {
+                        format!("  **{}**: {}\n", col.name, comment.replace('\n', " "))
+                    })
+                })
+                .collect();
+            if !columns.is_empty() && !res.is_empty() {
+                res += "  ..\n";
+            }
+            res += &columns.concat();
+        }
+        (!res.is_empty()).then_some(res)
+    }
+
+    fn view_render(
+        &self,
+        view: &View,
+        opts: &GeneratorConfigOptions,
+    ) -> Result<String, crate::SqlantError> {
+        let conceptual = opts.conceptual_diagram;
         let columns_render = |columns: Vec<Arc<TableColumn>>| -> Result<String, _> {
             Ok::<std::string::String, crate::SqlantError>(columns.iter().try_fold(
                 String::new(),
@@ -234,6 +412,10 @@ impl<'a> PlantUmlDefaultGenerator<'a> {
                             is_pk: false,
                             is_nn: false,
                             is_nn_and_not_pk: false,
+                            is_uq: false,
+                            default: None,
+                            check: None,
+                            diff: None,
                         },
                     );
                     match r {
@@ -257,6 +439,16 @@ impl<'a> PlantUmlDefaultGenerator<'a> {
                 columns: columns_render_if_not_conceptual(view.columns.clone())?,
                 name: view.name.clone(),
                 materialized: view.materialized,
+                color: opts
+                    .diff
+                    .as_ref()
+                    .and_then(|diff| diff.view_change(&view.name))
+                    .map(background_color),
+                comments: if opts.draw_comments {
+                    Self::note(view.comment.as_deref(), &view.columns, conceptual)
+                } else {
+                    None
+                },
             },
         )?)
     }
@@ -268,16 +460,42 @@ impl ViewGenerator for PlantUmlDefaultGenerator<'_> {
         sql_erd: SqlERData,
         opts: &GeneratorConfigOptions,
     ) -> Result<String, crate::SqlantError> {
-        let entities: Vec<String> = sql_erd
-            .tables
-            .iter()
-            .map(|tbl| self.entity_render(tbl, opts.conceptual_diagram))
-            .collect::<Result<Vec<String>, crate::SqlantError>>()?;
-        let views: Vec<String> = sql_erd
-            .views
-            .iter()
-            .map(|view| self.view_render(view, opts.conceptual_diagram))
-            .collect::<Result<Vec<String>, crate::SqlantError>>()?;
+        let entities_of = |schema: Option<&str>| {
+            sql_erd
+                .tables
+                .iter()
+                .filter(|tbl| schema.is_none() || tbl.schema.as_deref() == schema)
+                .map(|tbl| self.entity_render(tbl, opts))
+                .collect::<Result<Vec<String>, crate::SqlantError>>()
+        };
+        let views_of = |schema: Option<&str>| {
+            sql_erd
+                .views
+                .iter()
+                .filter(|view| schema.is_none() || view.schema.as_deref() == schema)
+                .map(|view| self.view_render(view, opts))
+                .collect::<Result<Vec<String>, crate::SqlantError>>()
+        };
+
+        let schemas = sql_erd.schemas();
+        let (packages, entities, views) = if schemas.len() > 1 {
+            let packages = schemas
+                .into_iter()
+                .map(|schema| {
+                    Ok(self.str_templates.render(
+                        "package",
+                        &SPackage {
+                            name: schema,
+                            entities: entities_of(Some(schema))?,
+                            views: views_of(Some(schema))?,
+                        },
+                    )?)
+                })
+                .collect::<Result<Vec<String>, crate::SqlantError>>()?;
+            (packages, vec![], vec![])
+        } else {
+            (vec![], entities_of(None)?, views_of(None)?)
+        };
 
         let foreign_keys: Vec<String> = sql_erd
             .foreign_keys
@@ -289,6 +507,43 @@ impl ViewGenerator for PlantUmlDefaultGenerator<'_> {
                         source_table_name: fk.source_table.name.clone(),
                         target_table_name: fk.target_table.name.clone(),
                         is_zero_one_to_one: fk.is_zero_one_to_one,
+                        // Changes of diff diagram take precedence over cascades
+                        color: opts
+                            .diff
+                            .as_ref()
+                            .and_then(|diff| diff.foreign_key_change(fk))
+                            .map(line_color)
+                            .or_else(|| {
+                                (opts.highlight_cascades
+                                    && fk.on_delete == ReferentialAction::Cascade)
+                                    .then_some(CASCADE_COLOR)
+                            }),
+                        label: fk
+                            .name
+                            .iter()
+                            .cloned()
+                            .chain(fk.clauses())
+                            .collect::<Vec<_>>()
+                            .join("\\n"),
+                    },
+                )
+            })
+            .collect::<Result<Vec<String>, _>>()?;
+
+        let dependencies: Vec<String> = sql_erd
+            .view_dependencies()
+            .into_iter()
+            .map(|(view, name)| {
+                self.str_templates.render(
+                    "dependency",
+                    &SDependency {
+                        view_name: &view.name,
+                        source_name: name,
+                        columns: view
+                            .used_columns
+                            .get(name)
+                            .filter(|_| opts.draw_lineage)
+                            .map(|columns| columns.join(", ")),
                     },
                 )
             })
@@ -304,6 +559,11 @@ impl ViewGenerator for PlantUmlDefaultGenerator<'_> {
                         &SSqlEnum {
                             name: name.to_string(),
                             values: values.to_vec(),
+                            color: opts
+                                .diff
+                                .as_ref()
+                                .and_then(|diff| diff.enum_change(name))
+                                .map(background_color),
                         },
                     )
                 })
@@ -334,8 +594,10 @@ impl ViewGenerator for PlantUmlDefaultGenerator<'_> {
             "puml",
             &SPuml {
                 puml_lib,
+                packages,
                 entities,
                 foreign_keys,
+                dependencies,
                 enums,
                 legend,
                 views,
@@ -344,12 +606,17 @@ impl ViewGenerator for PlantUmlDefaultGenerator<'_> {
         )?)
     }
 }
-static PUML_LIB_INCLUDE: &str = "!include https://raw.githubusercontent.com/kurotych/sqlant/9b19d6691b55c838b0809ed66707e61533a4c9f2/puml-lib/db_ent.puml";
+// Has to be in sync with PUML_LIB_INLINE and puml-lib/db_ent.puml.
+// Pinned to a tag, so the library is kept after the commit is rebased or squashed.
+// A new tag has to be created and pushed when the library is changed.
+static PUML_LIB_INCLUDE: &str =
+    "!include https://raw.githubusercontent.com/kurotych/sqlant/puml-lib-1/puml-lib/db_ent.puml";
 
-// https://raw.githubusercontent.com/kurotych/sqlant/0497c6594364e406d77dfdc0999e0b5e596b7d73/puml-lib/db_ent.puml
+// https://raw.githubusercontent.com/kurotych/sqlant/puml-lib-1/puml-lib/db_ent.puml
 static PUML_LIB_INLINE: &str = r#"
-!function column($name, $type, $pk=false, $fk=false, $nn=false)
+!function column($name, $type, $pk=false, $fk=false, $nn=false, $uq=false, $default="", $check="", $diff="")
   !local $prefix = ""
+  !local $suffix = ""
 
   !if ($pk == true)
     !$prefix = "<color:#d99d1c><&key></color>"
@@ -361,7 +628,51 @@ static PUML_LIB_INLINE: &str = r#"
     !$prefix = $prefix + "<color:#aaaaaa><&key></color>"
   !endif
 
-  !return $prefix + '<b>""' + $name + '""</b>' + ': ' + '//""' + $type + '"" //'
+  !if ($uq == true)
+    !$suffix = $suffix + " <color:#3a7bd5>**(U)**</color>"
+  !endif
+
+  !if ($default != "")
+    !$suffix = $suffix + ' = ""' + $default + '""'
+  !endif
+
+  !if ($check != "")
+    !$suffix = $suffix + ' <color:#888888>CHECK(""' + $check + '"")</color>'
+  !endif
+
+  !local $res = $prefix + '<b>""' + $name + '""</b>' + ': ' + '//""' + $type + '"" //' + $suffix
+
+  !if ($diff == "added")
+    !$res = "<color:#2E8B57>" + $res + "</color>"
+  !elseif ($diff == "removed")
+    !$res = "<color:#D9534F><s>" + $res + "</s></color>"
+  !elseif ($diff == "changed")
+    !$res = "<color:#E69500>" + $res + "</color>"
+  !endif
+
+  !return $res
+!endfunction
+
+!function index($name, $columns, $unique=false, $method="btree", $include="", $predicate="")
+  !local $res = '<color:#aaaaaa><&list></color>""' + $name + '""' + ': ' + '//""(' + $columns + ')""//'
+
+  !if ($unique == true)
+    !$res = $res + " <color:#3a7bd5>**(U)**</color>"
+  !endif
+
+  !if ($method != "btree")
+    !$res = $res + " " + $method
+  !endif
+
+  !if ($include != "")
+    !$res = $res + ' INCLUDE ""(' + $include + ')""'
+  !endif
+
+  !if ($predicate != "")
+    !$res = $res + ' WHERE ""' + $predicate + '""'
+  !endif
+
+  !return $res
 !endfunction
 
 !function table($name)
@@ -376,10 +687,10 @@ static PUML_LIB_INLINE: &str = r#"
   !endif
 !endfunction
 
-!procedure enum($name, $variants)
+!procedure enum($name, $variants, $color="")
   !$list = %splitstr($variants, ",")
 
-  object "**$name** <color:purple>**(E)**</color>" as $name {
+  object "**$name** <color:purple>**(E)**</color>" as $name $color {
     !foreach $item in $list
       $item
     !endfor
@@ -392,6 +703,7 @@ static PUML_LIB_INLINE: &str = r#"
    |<b><color:#b8861b><&key></color></b>| Primary Key |
    |<color:#aaaaaa><&key></color>| Foreign Key |
    | &#8226; | Mandatory field (Not Null) |
+   | <color:#3a7bd5>**(U)**</color> | Unique |
    | <color:purple>**(E)**</color> | Enum |
    | <color:SkyBlue>**(V)**</color> | View |
    | <color:DarkBlue>**(MV)**</color> | Materialized View |
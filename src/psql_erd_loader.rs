@@ -1,8 +1,7 @@
-use native_tls::TlsConnector;
-use postgres_native_tls::MakeTlsConnector;
 use std::{
     collections::{BTreeMap, BTreeSet},
     error::Error,
+    str::FromStr,
     sync::Arc,
 };
 use tokio_postgres::{
@@ -13,26 +12,79 @@ use tokio_postgres::{
 use crate::sql_entities::View;
 use crate::{
     sql_entities::{
-        ColumnConstraints, ForeignKey, SqlERData, SqlERDataLoader, SqlEnums, Table, TableColumn,
+        mark_foreign_key_columns, ColumnConstraints, ForeignKey, MatchType, ReferentialAction,
+        SqlERData, SqlERDataLoader, SqlEnums, Table, TableColumn, TableIndex,
     },
+    table_filter::TableFilter,
+    tls::TlsConfig,
     SqlantError,
 };
 
 static GET_TABLES_LIST_QUERY: &str = r#"
-SELECT trim(both '"' from table_name) as table_name, table_type
+SELECT table_schema as schema_name,
+       trim(both '"' from table_name) as table_name,
+       table_type
 FROM information_schema.tables
-WHERE table_schema = $1
-ORDER BY table_name;
+WHERE table_schema = any($1)
+ORDER BY table_schema, table_name;
 "#;
 
 static GET_MATERIALIZED_VIEWS: &str = r#"
-SELECT trim(both '"' from matviewname) AS matview_name
+SELECT schemaname AS schema_name,
+       trim(both '"' from matviewname) AS matview_name
 FROM pg_matviews
-WHERE schemaname = $1
-ORDER BY matviewname;
+WHERE schemaname = any($1)
+ORDER BY schemaname, matviewname;
+"#;
+
+/// Tables, views and their columns that are used by rewrite rule (query)
+/// of view or materialized view. Column is NULL for dependency on the whole relation.
+/// https://www.postgresql.org/docs/current/catalog-pg-depend.html
+static GET_VIEW_DEPENDENCIES_QUERY: &str = r#"
+SELECT DISTINCT view_ns.nspname   AS view_schema_name,
+       view.relname               AS view_name,
+       source_ns.nspname          AS source_schema_name,
+       source.relname             AS source_name,
+       source_column.attname      AS source_column_name
+FROM pg_rewrite
+INNER JOIN pg_class view
+   ON view.oid = pg_rewrite.ev_class
+INNER JOIN pg_namespace view_ns
+   ON view_ns.oid = view.relnamespace
+INNER JOIN pg_depend
+   ON pg_depend.objid = pg_rewrite.oid
+  AND pg_depend.classid = 'pg_rewrite'::regclass
+  AND pg_depend.refclassid = 'pg_class'::regclass
+INNER JOIN pg_class source
+   ON source.oid = pg_depend.refobjid
+INNER JOIN pg_namespace source_ns
+   ON source_ns.oid = source.relnamespace
+LEFT JOIN pg_attribute source_column
+   ON source_column.attrelid = source.oid
+  AND source_column.attnum = pg_depend.refobjsubid
+  AND source_column.attnum > 0
+  AND NOT source_column.attisdropped
+WHERE view.relkind IN ('v', 'm')
+AND   source.oid <> view.oid
+AND   view_ns.nspname = any($1)
+AND   source_ns.nspname = any($1)
+ORDER BY view_schema_name, view_name, source_schema_name, source_name, source_column_name;
+"#;
+
+static CHECK_SCHEMA_EXISTS_QUERY: &str =
+    "SELECT EXISTS(SELECT 1 FROM pg_namespace WHERE nspname = $1)";
+
+static GET_ALL_SCHEMAS_QUERY: &str = r#"
+SELECT nspname AS schema_name
+FROM pg_namespace
+WHERE nspname NOT IN ('pg_catalog', 'information_schema')
+AND nspname NOT LIKE 'pg\_toast%'
+AND nspname NOT LIKE 'pg\_temp\_%'
+ORDER BY nspname;
 "#;
 
 /// https://www.postgresql.org/docs/current/catalog-pg-attribute.html
+/// https://www.postgresql.org/docs/current/functions-info.html#FUNCTIONS-INFO-COMMENT
 static GET_COLUMNS_BASIC_INFO_QUERY: &str = r#"
 SELECT attname                                  AS col_name,
        attnum                                   AS col_num,
@@ -40,13 +92,22 @@ SELECT attname                                  AS col_name,
        attnotnull                               AS not_null,
        relname                                  AS table_name,
        pg_type.oid                              AS column_type_oid,
-       typtype
+       typtype,
+       typname,
+       type_ns.nspname                          AS type_schema_name,
+       col_description(pga.attrelid, attnum)    AS col_comment,
+       obj_description(pg_class.oid, 'pg_class') AS table_comment
 FROM   pg_attribute pga
 INNER JOIN pg_class
    ON pg_class.oid = pga.attrelid
+INNER JOIN pg_namespace
+   ON pg_namespace.oid = pg_class.relnamespace
 INNER JOIN pg_type
    ON pga.atttypid::regtype::oid = pg_type.oid
-WHERE relname =  any($1)
+INNER JOIN pg_namespace type_ns
+   ON type_ns.oid = pg_type.typnamespace
+WHERE pg_namespace.nspname = $1
+AND    relname =  any($2)
 AND    NOT attisdropped
 AND    attnum  > 0
 ORDER  BY relname, col_name;
@@ -55,27 +116,53 @@ ORDER  BY relname, col_name;
 // pg_get_constraintdef(oid) -- just for debugging purposes
 /// https://www.postgresql.org/docs/current/catalog-pg-constraint.html
 static GET_FOREIGN_KEYS_QUERY: &str = r#"
-SELECT trim(both '"' from conrelid::regclass::name)  AS source_table_name,
-       trim(both '"' from confrelid::regclass::name) AS target_table_name,
-       conname                   AS foreign_key_name,
-       conkey                    AS source_column_nums,
-       confkey                   AS target_columns_nums,
-       pg_get_constraintdef(oid) -- just for debugging purposes
+SELECT source_ns.nspname          AS source_schema_name,
+       source.relname             AS source_table_name,
+       target_ns.nspname          AS target_schema_name,
+       target.relname             AS target_table_name,
+       conname                    AS foreign_key_name,
+       conkey                     AS source_column_nums,
+       confkey                    AS target_columns_nums,
+       ARRAY(SELECT attname::text FROM unnest(conkey) WITH ORDINALITY AS k(num, i)
+             INNER JOIN pg_attribute ON attrelid = conrelid AND attnum = k.num
+             ORDER BY k.i) AS source_column_names,
+       ARRAY(SELECT attname::text FROM unnest(confkey) WITH ORDINALITY AS k(num, i)
+             INNER JOIN pg_attribute ON attrelid = confrelid AND attnum = k.num
+             ORDER BY k.i) AS target_column_names,
+       confdeltype                AS on_delete,
+       confupdtype                AS on_update,
+       condeferrable              AS deferrable,
+       condeferred                AS initially_deferred,
+       confmatchtype              AS match_type,
+       pg_get_constraintdef(pg_constraint.oid) -- just for debugging purposes
 FROM   pg_constraint
+INNER JOIN pg_class source
+   ON source.oid = conrelid
+INNER JOIN pg_namespace source_ns
+   ON source_ns.oid = source.relnamespace
+INNER JOIN pg_class target
+   ON target.oid = confrelid
+INNER JOIN pg_namespace target_ns
+   ON target_ns.oid = target.relnamespace
 WHERE  contype = 'f'
-AND    connamespace = to_regnamespace($1)::oid
-ORDER  BY source_table_name;
+AND    source_ns.nspname = any($1)
+ORDER  BY source_schema_name, source_table_name;
 "#;
 
 static GET_PKS_QUERY: &str = r#"
-SELECT trim(both '"' from conrelid::regclass::name) as table_name,
+SELECT nspname                  AS schema_name,
+       relname                  AS table_name,
        conname                  AS primary_key_name,
        conkey                   AS pk_columns_nums,
-       pg_get_constraintdef(oid) -- just for debugging purposes
+       pg_get_constraintdef(pg_constraint.oid) -- just for debugging purposes
 FROM   pg_constraint
+INNER JOIN pg_class
+   ON pg_class.oid = conrelid
+INNER JOIN pg_namespace
+   ON pg_namespace.oid = pg_class.relnamespace
 WHERE  contype = 'p'
-AND    connamespace = to_regnamespace($1)::oid
-ORDER  BY table_name;
+AND    nspname = any($1)
+ORDER  BY schema_name, table_name;
 "#;
 
 static GET_ENUM_VALUES: &str = r#"
@@ -86,49 +173,160 @@ WHERE pg_type.oid = $1
 ORDER BY pg_enum;
 "#;
 
-// https://www.postgresql.org/docs/current/view-pg-indexes.html
-// If you'll need to add indexers support
-// static GET_INDEXES_QUERY: &'static str = r#"
-// SELECT
-//     tablename,
-//     indexname,
-//     indexdef
-// FROM
-//     pg_indexes
-// WHERE
-//     schemaname = 'public'
-// ORDER BY
-//     tablename,
-//     indexname;
-// "#;
+/// https://www.postgresql.org/docs/current/catalog-pg-constraint.html
+static GET_CHECKS_AND_UNIQUES_QUERY: &str = r#"
+SELECT nspname                       AS schema_name,
+       relname                       AS table_name,
+       contype::text                 AS constraint_type,
+       conkey                        AS columns_nums,
+       pg_get_expr(conbin, conrelid) AS check_expression
+FROM   pg_constraint
+INNER JOIN pg_class
+   ON pg_class.oid = conrelid
+INNER JOIN pg_namespace
+   ON pg_namespace.oid = pg_class.relnamespace
+WHERE  contype IN ('c', 'u')
+AND    nspname = any($1)
+ORDER  BY schema_name, table_name, conname;
+"#;
+
+/// https://www.postgresql.org/docs/current/catalog-pg-attrdef.html
+static GET_DEFAULTS_QUERY: &str = r#"
+SELECT nspname                      AS schema_name,
+       relname                      AS table_name,
+       adnum                        AS col_num,
+       pg_get_expr(adbin, adrelid)  AS default_value
+FROM   pg_attrdef
+INNER JOIN pg_class
+   ON pg_class.oid = adrelid
+INNER JOIN pg_namespace
+   ON pg_namespace.oid = pg_class.relnamespace
+WHERE  nspname = any($1)
+ORDER  BY schema_name, table_name, col_num;
+"#;
+
+/// https://www.postgresql.org/docs/current/catalog-pg-index.html
+/// Expression columns of index have 0 in indkey.
+/// The first `key_columns_count` of `columns` are key columns, others are from INCLUDE
+static GET_INDEXES_QUERY: &str = r#"
+SELECT nspname                                       AS schema_name,
+       tbl.relname                                   AS table_name,
+       idx.relname                                   AS index_name,
+       indisunique                                   AS is_unique,
+       amname                                        AS method,
+       pg_get_expr(indpred, indrelid)                AS predicate,
+       indnkeyatts                                   AS key_columns_count,
+       indkey::int2[]                                AS columns_nums,
+       ARRAY(SELECT pg_get_indexdef(indexrelid, k + 1, true)
+             FROM generate_subscripts(indkey, 1) AS k
+             ORDER BY k)                             AS columns,
+       EXISTS (SELECT 1 FROM pg_constraint
+               WHERE conindid = indexrelid
+               -- Foreign keys refer to indexes of referenced tables
+               AND contype IN ('p', 'u', 'x'))       AS is_constraint
+FROM   pg_index
+INNER JOIN pg_class tbl
+   ON tbl.oid = indrelid
+INNER JOIN pg_class idx
+   ON idx.oid = indexrelid
+INNER JOIN pg_am
+   ON pg_am.oid = idx.relam
+INNER JOIN pg_namespace
+   ON pg_namespace.oid = tbl.relnamespace
+WHERE  nspname = any($1)
+ORDER  BY schema_name, table_name, index_name;
+"#;
 
 /// Internal type of Foreign Key. With values that loaded from db
 #[derive(Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
 struct FkInternal {
+    source_schema_name: String,
     source_table_name: String,
     source_columns_num: Vec<i16>,
+    target_schema_name: String,
     target_table_name: String,
     target_columns_num: Vec<i16>,
+    // Names of columns are used only to report dangling FK
+    source_column_names: Vec<String>,
+    target_column_names: Vec<String>,
+    name: String,
+    // Codes of pg_constraint. Ex: 'c' - cascade
+    on_delete: i8,
+    on_update: i8,
+    deferrable: bool,
+    initially_deferred: bool,
+    match_type: i8,
 }
 
+// https://www.postgresql.org/docs/current/catalog-pg-constraint.html
+fn referential_action(code: i8) -> ReferentialAction {
+    match code as u8 {
+        b'r' => ReferentialAction::Restrict,
+        b'c' => ReferentialAction::Cascade,
+        b'n' => ReferentialAction::SetNull,
+        b'd' => ReferentialAction::SetDefault,
+        _ => ReferentialAction::NoAction, // 'a'
+    }
+}
+
+fn match_type(code: i8) -> MatchType {
+    match code as u8 {
+        b'f' => MatchType::Full,
+        b'p' => MatchType::Partial,
+        _ => MatchType::Simple, // 's'
+    }
+}
+
+// Columns are in order of numbers, which is order of columns in constraint
+fn columns_by_nums(table: &Table, col_nums: &[i16]) -> Vec<Arc<TableColumn>> {
+    col_nums
+        .iter()
+        .filter_map(|num| table.columns.iter().find(|col| col.col_num == *num))
+        .map(Arc::clone)
+        .collect()
+}
+
+// (schema_name, table_name)
+type QualifiedName = (String, String);
+
 pub struct PostgreSqlERDLoader {
     client: Client,
-    schema_name: String,
-    pks: BTreeMap<String, Vec<i16>>, // table_name, col_nums
-    fks: BTreeMap<String, BTreeSet<FkInternal>>, // key - source_table_name
+    schema_names: Vec<String>,
+    pks: BTreeMap<QualifiedName, Vec<i16>>, // col_nums
+    fks: BTreeMap<QualifiedName, BTreeSet<FkInternal>>, // key - source table
+    // CHECK, UNIQUE, DEFAULT and Index constraints. key - table, col_num
+    constraints: BTreeMap<QualifiedName, BTreeMap<i16, BTreeSet<ColumnConstraints>>>,
+    indexes: BTreeMap<QualifiedName, Vec<TableIndex>>,
+    table_filter: TableFilter,
 }
 
 impl PostgreSqlERDLoader {
+    /// `schema_names` can contain "*" to load all schemas of database.
+    /// If several schemas are loaded, names of entities are qualified as `schema.table`.
     pub async fn new(
         connection_string: &str,
-        schema_name: String,
+        schema_names: Vec<String>,
     ) -> Result<PostgreSqlERDLoader, SqlantError> {
-        let connector = TlsConnector::builder()
-            .danger_accept_invalid_certs(true)
-            .build()?;
-        let connector = MakeTlsConnector::new(connector);
+        Self::with_tls(connection_string, schema_names, TlsConfig::default()).await
+    }
 
-        let (client, connection) = tokio_postgres::connect(connection_string, connector).await?;
+    /// Options of `tls_config` take precedence over TLS parameters of connection string
+    pub async fn with_tls(
+        connection_string: &str,
+        schema_names: Vec<String>,
+        tls_config: TlsConfig,
+    ) -> Result<PostgreSqlERDLoader, SqlantError> {
+        let (connection_string, connection_tls_config) =
+            TlsConfig::from_connection_string(connection_string)?;
+        let tls_config = tls_config.or(connection_tls_config);
+        let mut config = tokio_postgres::Config::from_str(&connection_string)
+            .map_err(SqlantError::connection)?;
+        config.ssl_mode(tls_config.postgres_ssl_mode());
+
+        let (client, connection) = config
+            .connect(tls_config.connector()?)
+            .await
+            .map_err(SqlantError::connection)?;
         // The connection object performs the actual communication with the database,
         // so spawn it off to run on its own.
         tokio::spawn(async move {
@@ -138,59 +336,93 @@ impl PostgreSqlERDLoader {
         });
         Ok(PostgreSqlERDLoader {
             client,
-            schema_name,
+            schema_names,
             pks: BTreeMap::new(),
             fks: BTreeMap::new(),
+            constraints: BTreeMap::new(),
+            indexes: BTreeMap::new(),
+            table_filter: TableFilter::default(),
         })
     }
 
-    /// Return empty vector if no FKs
-    fn get_fks(&self, tbls: &Vec<Arc<Table>>) -> Result<Vec<ForeignKey>, crate::SqlantError> {
-        let mut res = vec![];
-        for tbl in tbls {
-            if let Some(fks) = self.fks.get(&tbl.name) {
-                for fk in fks {
-                    let source_table = Arc::clone(tbl);
-
-                    let source_columns: Vec<Arc<TableColumn>> = source_table
-                        .columns
-                        .iter()
-                        .filter(|&col| fk.source_columns_num.contains(&col.col_num))
-                        .map(Arc::clone)
-                        .collect();
-
-                    let target_table = Arc::clone(
-                        tbls.iter()
-                            .find(|&tbl| tbl.name == fk.target_table_name)
-                            .ok_or(SqlantError::PsqlErdLoader(
-                                "Target table is not found".to_string(),
-                            ))?,
-                    );
+    /// Filtered out tables and views are not loaded at all.
+    /// Foreign keys from or to them are skipped.
+    pub fn with_table_filter(mut self, table_filter: TableFilter) -> PostgreSqlERDLoader {
+        self.table_filter = table_filter;
+        self
+    }
 
-                    let target_columns: Vec<Arc<TableColumn>> = target_table
-                        .columns
-                        .iter()
-                        .filter(|&col| fk.target_columns_num.contains(&col.col_num))
-                        .map(Arc::clone)
-                        .collect();
-
-                    res.push(ForeignKey::new(
-                        source_table,
-                        source_columns,
-                        target_table,
-                        target_columns,
+    fn entity_name(&self, schema_name: &str, table_name: &str) -> String {
+        if self.schema_names.len() > 1 {
+            format!("{schema_name}.{table_name}")
+        } else {
+            table_name.to_string()
+        }
+    }
+
+    fn find_table(
+        &self,
+        tbls: &[Arc<Table>],
+        schema_name: &str,
+        table_name: &str,
+    ) -> Option<Arc<Table>> {
+        let name = self.entity_name(schema_name, table_name);
+        tbls.iter()
+            .find(|&tbl| tbl.name == name && tbl.schema.as_deref() == Some(schema_name))
+            .map(Arc::clone)
+    }
+
+    /// Return empty vector if no FKs.
+    /// FKs from or to tables that are not loaded on purpose (filtered out or in other schemas)
+    /// are skipped, other FKs to missing tables are reported as dangling.
+    fn get_fks(&self, tbls: &[Arc<Table>]) -> Result<Vec<ForeignKey>, SqlantError> {
+        let is_loaded = |schema_name: &String, table_name: &str| {
+            self.schema_names.contains(schema_name)
+                && self.table_filter.is_match(Some(schema_name), table_name)
+        };
+        let mut res = vec![];
+        for fk in self.fks.values().flatten() {
+            let (Some(source_table), Some(target_table)) = (
+                self.find_table(tbls, &fk.source_schema_name, &fk.source_table_name),
+                self.find_table(tbls, &fk.target_schema_name, &fk.target_table_name),
+            ) else {
+                if is_loaded(&fk.source_schema_name, &fk.source_table_name)
+                    && is_loaded(&fk.target_schema_name, &fk.target_table_name)
+                {
+                    return Err(SqlantError::dangling_foreign_key(
+                        &self.entity_name(&fk.source_schema_name, &fk.source_table_name),
+                        &fk.source_column_names,
+                        &self.entity_name(&fk.target_schema_name, &fk.target_table_name),
+                        &fk.target_column_names,
                     ));
                 }
-            }
+                continue;
+            };
+
+            let source_columns = columns_by_nums(&source_table, &fk.source_columns_num);
+            let target_columns = columns_by_nums(&target_table, &fk.target_columns_num);
+
+            res.push(
+                ForeignKey::new(source_table, source_columns, target_table, target_columns)
+                    .with_name(Some(fk.name.clone()))
+                    .with_actions(
+                        referential_action(fk.on_delete),
+                        referential_action(fk.on_update),
+                    )
+                    .with_deferrable(fk.deferrable, fk.initially_deferred)
+                    .with_match_type(match_type(fk.match_type)),
+            );
         }
         Ok(res)
     }
 
     async fn load_tables(
         &mut self,
+        schema_name: &str,
         table_names: Vec<String>,
     ) -> Result<(Vec<Arc<Table>>, SqlEnums), crate::SqlantError> {
         let mut columns: BTreeMap<String, Vec<Arc<TableColumn>>> = BTreeMap::new();
+        let mut comments: BTreeMap<String, String> = BTreeMap::new();
         // If current database has enum types we load it here
         let mut enums: SqlEnums = BTreeMap::new();
         for tbl_name in &table_names {
@@ -199,79 +431,86 @@ impl PostgreSqlERDLoader {
 
         let rows = self
             .client
-            .query(GET_COLUMNS_BASIC_INFO_QUERY, &[&table_names])
-            .await?;
+            .query(GET_COLUMNS_BASIC_INFO_QUERY, &[&schema_name, &table_names])
+            .await
+            .map_err(SqlantError::query(GET_COLUMNS_BASIC_INFO_QUERY))?;
         for row in rows {
             let col_num: i16 = row.get("col_num");
             let col_name: &str = row.get("col_name");
             let not_null: bool = row.get("not_null");
             let tbl_name: &str = row.get("table_name");
-            let col_type: &str = row.get("datatype");
+            let mut col_type: String = row.get("datatype");
+            if let Some(table_comment) = row.get::<_, Option<String>>("table_comment") {
+                comments.insert(tbl_name.to_string(), table_comment);
+            }
 
             let typ_type: i8 = row.get("typtype");
             let tt: u32 = typ_type
                 .try_into()
-                .map_err(|e: <u32 as TryFrom<i32>>::Error| {
-                    crate::SqlantError::PsqlErdLoader(e.to_string())
-                })?;
-            let ttc: char = tt.try_into().map_err(|e: <char as TryFrom<u32>>::Error| {
-                crate::SqlantError::PsqlErdLoader(e.to_string())
-            })?;
-
-            if ttc == 'e' && !enums.contains_key(col_type) {
+                .map_err(|e: <u32 as TryFrom<i32>>::Error| SqlantError::Internal(e.to_string()))?;
+            let ttc: char = tt
+                .try_into()
+                .map_err(|e: <char as TryFrom<u32>>::Error| SqlantError::Internal(e.to_string()))?;
+
+            // Enums of different schemas can have the same name
+            if ttc == 'e' && self.schema_names.len() > 1 {
+                col_type = self.entity_name(row.get("type_schema_name"), row.get("typname"));
+            }
+            if ttc == 'e' && !enums.contains_key(&col_type) {
                 let column_type_oid: u32 = row.get("column_type_oid");
                 let enum_values = self
                     .client
                     .query(GET_ENUM_VALUES, &[&column_type_oid])
-                    .await?;
+                    .await
+                    .map_err(SqlantError::query(GET_ENUM_VALUES))?;
 
                 let vals: Vec<String> = enum_values.iter().map(|v| v.get("enumlabel")).collect();
-                enums.insert(col_type.to_string(), vals);
+                enums.insert(col_type.clone(), vals);
             }
 
-            let mut constraints = self.get_constraints(tbl_name, col_num);
+            let mut constraints = self.get_constraints(schema_name, tbl_name, col_num);
             if not_null {
                 constraints.insert(ColumnConstraints::NotNull);
             }
 
             columns
                 .get_mut(tbl_name)
-                .ok_or(SqlantError::PsqlErdLoader(
+                .ok_or(SqlantError::Internal(
                     "Failed to get mut columns".to_string(),
                 ))?
                 .push(Arc::new(TableColumn {
                     name: col_name.to_string(),
                     col_num,
-                    datatype: col_type.to_string(),
+                    datatype: col_type,
                     constraints,
+                    comment: row.get("col_comment"),
                 }));
         }
         // Transform BTreeMap<String, Vec<Arc<TableColumn>>> into Vec<Table>
         Ok((
             columns
                 .iter()
-                .map(|(k, v)| Arc::new(Table::new(k.to_string(), v.to_vec())))
+                .map(|(k, v)| {
+                    let mut tbl = Table::new(self.entity_name(schema_name, k), v.to_vec());
+                    tbl.schema = Some(schema_name.to_string());
+                    tbl.indexes = self
+                        .indexes
+                        .get(&(schema_name.to_string(), k.to_string()))
+                        .cloned()
+                        .unwrap_or_default();
+                    tbl.comment = comments.get(k).cloned();
+                    Arc::new(tbl)
+                })
                 .collect(),
             enums,
         ))
     }
 
-    fn is_fk(&mut self, table_name: &str, table_column: i16) -> bool {
-        match self.fks.get(table_name) {
-            None => false,
-            Some(fks) => {
-                for f in fks {
-                    if f.source_columns_num.contains(&table_column) {
-                        return true;
-                    }
-                }
-                false
-            }
-        }
-    }
-
-    fn is_pk(&mut self, table_name: &str, table_column: i16) -> bool {
-        match self.pks.get(table_name) {
+    fn is_pk(&mut self, schema_name: &str, table_name: &str, table_column: i16) -> bool {
+        match self
+            .pks
+            .get(&(schema_name.to_string(), table_name.to_string()))
+        {
             None => false,
             Some(cols) => cols.contains(&table_column),
         }
@@ -279,32 +518,144 @@ impl PostgreSqlERDLoader {
 
     fn get_constraints(
         &mut self,
+        schema_name: &str,
         table_name: &str,
         table_column: i16,
     ) -> BTreeSet<ColumnConstraints> {
         let mut res = BTreeSet::new();
-        if self.is_pk(table_name, table_column) {
+        if self.is_pk(schema_name, table_name, table_column) {
             // The PRIMARY KEY of a table is a combination of NOT NULL and UNIQUE constraint.
             res.insert(ColumnConstraints::PrimaryKey);
             res.insert(ColumnConstraints::NotNull);
             res.insert(ColumnConstraints::Unique);
-        } // todo else if NotNull and if Unique
+        }
 
-        if self.is_fk(table_name, table_column) {
-            res.insert(ColumnConstraints::ForeignKey);
+        if let Some(constraints) = self
+            .constraints
+            .get(&(schema_name.to_string(), table_name.to_string()))
+            .and_then(|cols| cols.get(&table_column))
+        {
+            res.extend(constraints.iter().cloned());
         }
-        // TODO other constraints
         res
     }
 
+    fn add_constraint(
+        &mut self,
+        schema_name: String,
+        table_name: String,
+        col_nums: &[i16],
+        constraint: ColumnConstraints,
+    ) {
+        let cols = self
+            .constraints
+            .entry((schema_name, table_name))
+            .or_default();
+        for col_num in col_nums {
+            cols.entry(*col_num).or_default().insert(constraint.clone());
+        }
+    }
+
+    async fn load_constraints(&mut self) -> Result<(), SqlantError> {
+        let rows = self
+            .client
+            .query(GET_CHECKS_AND_UNIQUES_QUERY, &[&self.schema_names])
+            .await
+            .map_err(SqlantError::query(GET_CHECKS_AND_UNIQUES_QUERY))?;
+        for row in rows {
+            let constraint_type: &str = row.get("constraint_type");
+            let col_nums: Option<Vec<i16>> = row.get("columns_nums");
+            let constraint = match constraint_type {
+                // Columns of multi-column UNIQUE aren't unique by themselves
+                "u" if col_nums.as_ref().is_some_and(|nums| nums.len() > 1) => continue,
+                "u" => ColumnConstraints::Unique,
+                _ => {
+                    let expression: &str = row.get("check_expression");
+                    ColumnConstraints::Check(strip_parentheses(expression).to_string())
+                }
+            };
+            self.add_constraint(
+                row.get("schema_name"),
+                row.get("table_name"),
+                &col_nums.unwrap_or_default(),
+                constraint,
+            );
+        }
+
+        let rows = self
+            .client
+            .query(GET_DEFAULTS_QUERY, &[&self.schema_names])
+            .await
+            .map_err(SqlantError::query(GET_DEFAULTS_QUERY))?;
+        for row in rows {
+            self.add_constraint(
+                row.get("schema_name"),
+                row.get("table_name"),
+                &[row.get("col_num")],
+                ColumnConstraints::Default(row.get("default_value")),
+            );
+        }
+
+        let rows = self
+            .client
+            .query(GET_INDEXES_QUERY, &[&self.schema_names])
+            .await
+            .map_err(SqlantError::query(GET_INDEXES_QUERY))?;
+        for row in rows {
+            let schema_name: String = row.get("schema_name");
+            let table_name: String = row.get("table_name");
+            let is_unique: bool = row.get("is_unique");
+            let key_columns_count: i16 = row.get("key_columns_count");
+            let key_columns_count = key_columns_count as usize;
+            let mut col_nums: Vec<i16> = row.get("columns_nums");
+            col_nums.truncate(key_columns_count);
+            let mut columns: Vec<String> = row.get("columns");
+            let include = columns.split_off(key_columns_count.min(columns.len()));
+
+            // Indexes of PRIMARY KEY and UNIQUE constraints are already taken into account
+            let is_constraint: bool = row.get("is_constraint");
+            let constraint = match (is_unique, col_nums.len()) {
+                (false, _) => Some(ColumnConstraints::Index),
+                (true, 1) => Some(ColumnConstraints::Unique),
+                _ => None,
+            };
+            if let Some(constraint) = constraint.filter(|_| !is_constraint) {
+                self.add_constraint(
+                    schema_name.clone(),
+                    table_name.clone(),
+                    &col_nums,
+                    constraint,
+                );
+            }
+
+            self.indexes
+                .entry((schema_name, table_name))
+                .or_default()
+                .push(TableIndex {
+                    name: row.get("index_name"),
+                    columns,
+                    unique: is_unique,
+                    method: row.get("method"),
+                    predicate: row
+                        .get::<_, Option<&str>>("predicate")
+                        .map(|p| strip_parentheses(p).to_string()),
+                    include,
+                });
+        }
+        Ok(())
+    }
+
     async fn load_pks(&mut self) -> Result<(), SqlantError> {
         for row in self
             .client
-            .query(GET_PKS_QUERY, &[&self.schema_name])
-            .await?
+            .query(GET_PKS_QUERY, &[&self.schema_names])
+            .await
+            .map_err(SqlantError::query(GET_PKS_QUERY))?
         {
-            self.pks
-                .insert(row.get("table_name"), row.get("pk_columns_nums"));
+            self.pks.insert(
+                (row.get("schema_name"), row.get("table_name")),
+                row.get("pk_columns_nums"),
+            );
         }
         Ok(())
     }
@@ -312,52 +663,178 @@ impl PostgreSqlERDLoader {
     async fn load_fks(&mut self) -> Result<(), SqlantError> {
         let rows = self
             .client
-            .query(GET_FOREIGN_KEYS_QUERY, &[&self.schema_name])
-            .await?;
+            .query(GET_FOREIGN_KEYS_QUERY, &[&self.schema_names])
+            .await
+            .map_err(SqlantError::query(GET_FOREIGN_KEYS_QUERY))?;
         for row in rows {
+            let source_schema_name: String = row.get("source_schema_name");
             let source_table_name: String = row.get("source_table_name");
+            let target_schema_name: String = row.get("target_schema_name");
             let target_table_name: String = row.get("target_table_name");
             let source_columns_num: Vec<i16> = row.get("source_column_nums");
             let target_columns_num: Vec<i16> = row.get("target_columns_nums");
-
+            let source_column_names: Vec<String> = row.get("source_column_names");
+            let target_column_names: Vec<String> = row.get("target_column_names");
+            let name: String = row.get("foreign_key_name");
+            let on_delete: i8 = row.get("on_delete");
+            let on_update: i8 = row.get("on_update");
+            let deferrable: bool = row.get("deferrable");
+            let initially_deferred: bool = row.get("initially_deferred");
+            let match_type: i8 = row.get("match_type");
+
+            let source = (source_schema_name.clone(), source_table_name.clone());
             let fk = FkInternal {
-                source_table_name: source_table_name.clone(),
+                source_schema_name,
+                source_table_name,
                 source_columns_num,
+                target_schema_name,
                 target_table_name,
                 target_columns_num,
+                source_column_names,
+                target_column_names,
+                name,
+                on_delete,
+                on_update,
+                deferrable,
+                initially_deferred,
+                match_type,
             };
-            if let Some(fks) = self.fks.get_mut(&source_table_name) {
+            if let Some(fks) = self.fks.get_mut(&source) {
                 fks.insert(fk);
             } else {
                 let mut hs = BTreeSet::new();
                 hs.insert(fk);
-                self.fks.insert(source_table_name, hs);
+                self.fks.insert(source, hs);
             }
         }
         Ok(())
     }
 
-    async fn check_is_schema_exists(&mut self) -> Result<(), SqlantError> {
+    async fn check_is_schema_exists(&self, schema_name: &str) -> Result<(), SqlantError> {
         let res = self
             .client
-            .query(
-                "SELECT EXISTS(SELECT 1 FROM pg_namespace WHERE nspname = $1)",
-                &[&self.schema_name],
-            )
-            .await?;
-        let row = res.first().ok_or(SqlantError::PsqlErdLoader(
+            .query(CHECK_SCHEMA_EXISTS_QUERY, &[&schema_name])
+            .await
+            .map_err(SqlantError::query(CHECK_SCHEMA_EXISTS_QUERY))?;
+        let row = res.first().ok_or(SqlantError::Internal(
             "check_is_schema_exists query doesn't return any row".to_string(),
         ))?;
         let exists: bool = row.get("exists");
         if !exists {
-            return Err(SqlantError::PsqlErdLoader(
-                "Schema doesn't exist".to_string(),
-            ));
+            return Err(SqlantError::SchemaNotFound {
+                schema: schema_name.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Sets `depends_on` and `used_columns` of views.
+    /// Dependencies on tables and views that are not loaded are skipped.
+    async fn load_view_dependencies(
+        &self,
+        tables: &[Arc<Table>],
+        views: Vec<Arc<View>>,
+    ) -> Result<Vec<Arc<View>>, SqlantError> {
+        let dependencies: Vec<(String, String, String, String, Option<String>)> = self
+            .client
+            .query(GET_VIEW_DEPENDENCIES_QUERY, &[&self.schema_names])
+            .await
+            .map_err(SqlantError::query(GET_VIEW_DEPENDENCIES_QUERY))?
+            .iter()
+            .map(|row| {
+                (
+                    row.get("view_schema_name"),
+                    row.get("view_name"),
+                    row.get("source_schema_name"),
+                    row.get("source_name"),
+                    row.get("source_column_name"),
+                )
+            })
+            .collect();
+        let is_loaded = |schema_name: &str, name: &str| {
+            let name = self.entity_name(schema_name, name);
+            tables
+                .iter()
+                .any(|t| t.name == name && t.schema.as_deref() == Some(schema_name))
+                || views
+                    .iter()
+                    .any(|v| v.name == name && v.schema.as_deref() == Some(schema_name))
+        };
+        // key - (schema, view), value - used columns of every dependency
+        let mut view_dependencies: BTreeMap<(String, String), BTreeMap<String, BTreeSet<String>>> =
+            BTreeMap::new();
+        for (view_schema, view_name, source_schema, source_name, column) in dependencies {
+            if is_loaded(&source_schema, &source_name) {
+                let columns = view_dependencies
+                    .entry((
+                        view_schema.clone(),
+                        self.entity_name(&view_schema, &view_name),
+                    ))
+                    .or_default()
+                    .entry(self.entity_name(&source_schema, &source_name))
+                    .or_default();
+                columns.extend(column);
+            }
+        }
+
+        Ok(views
+            .into_iter()
+            .map(|view| {
+                let key = (view.schema.clone().unwrap_or_default(), view.name.clone());
+                match view_dependencies.remove(&key) {
+                    Some(dependencies) => {
+                        let mut view = Arc::unwrap_or_clone(view);
+                        view.depends_on = dependencies.keys().cloned().collect();
+                        view.used_columns = dependencies
+                            .into_iter()
+                            .filter(|(_, columns)| !columns.is_empty())
+                            .map(|(name, columns)| (name, columns.into_iter().collect()))
+                            .collect();
+                        view.into()
+                    }
+                    None => view,
+                }
+            })
+            .collect())
+    }
+
+    /// Replaces "*" by all schemas of database
+    async fn resolve_schema_names(&mut self) -> Result<(), SqlantError> {
+        if self.schema_names.iter().any(|s| s == "*") {
+            self.schema_names = self
+                .client
+                .query(GET_ALL_SCHEMAS_QUERY, &[])
+                .await
+                .map_err(SqlantError::query(GET_ALL_SCHEMAS_QUERY))?
+                .iter()
+                .map(|row| row.get("schema_name"))
+                .collect();
         }
         Ok(())
     }
 }
 
+/// pg_get_expr wraps CHECK and index predicate expressions in parentheses: (price > (0)::numeric)
+fn strip_parentheses(expression: &str) -> &str {
+    let Some(inner) = expression
+        .strip_prefix('(')
+        .and_then(|e| e.strip_suffix(')'))
+    else {
+        return expression;
+    };
+    // (a > 0) AND (b > 0) -- parentheses don't wrap the whole expression
+    let mut depth = 0;
+    for c in inner.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return expression,
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    inner
+}
+
 #[derive(Debug, PartialEq)]
 enum TableType {
     BaseTable,
@@ -382,50 +859,91 @@ impl FromSql<'_> for TableType {
 #[async_trait::async_trait]
 impl SqlERDataLoader for PostgreSqlERDLoader {
     async fn load_erd_data(&mut self) -> Result<SqlERData, crate::SqlantError> {
-        // I use it to avoid adding schema prefixes in SQL queries
-        self.client
-            .query(&format!("SET search_path TO {}", self.schema_name), &[])
-            .await?;
-
-        self.check_is_schema_exists().await?;
+        self.resolve_schema_names().await?;
+        for schema_name in &self.schema_names {
+            self.check_is_schema_exists(schema_name).await?;
+        }
 
+        // I use it to avoid adding schema prefixes to datatypes (e.g. enums)
+        let search_path = self
+            .schema_names
+            .iter()
+            .map(|s| format!("\"{}\"", s.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let set_search_path = format!("SET search_path TO {search_path}");
+        self.client
+            .query(&set_search_path, &[])
+            .await
+            .map_err(SqlantError::query(&set_search_path))?;
+
+        // Loader can be used several times, so state of previous load is dropped
+        self.pks.clear();
+        self.fks.clear();
+        self.constraints.clear();
+        self.indexes.clear();
         self.load_pks().await?;
         self.load_fks().await?;
+        self.load_constraints().await?;
 
         let res = &self
             .client
-            .query(GET_TABLES_LIST_QUERY, &[&self.schema_name])
-            .await?;
-
-        // Collect table names and types as a vector of tuples
-        let table_names_with_types: Vec<(String, TableType)> = res
-            .iter()
-            .map(|row| (row.get("table_name"), row.get("table_type")))
-            .collect();
+            .query(GET_TABLES_LIST_QUERY, &[&self.schema_names])
+            .await
+            .map_err(SqlantError::query(GET_TABLES_LIST_QUERY))?;
 
-        // Extract just the table names for loading
-        let table_names: Vec<String> = table_names_with_types
+        // Collect schema, table names and types as a vector of tuples
+        let table_names_with_types: Vec<(String, String, TableType)> = res
             .iter()
-            .map(|(name, _)| name.clone())
+            .map(|row| {
+                (
+                    row.get("schema_name"),
+                    row.get("table_name"),
+                    row.get("table_type"),
+                )
+            })
+            .filter(|(schema, name, _): &(String, String, TableType)| {
+                self.table_filter.is_match(Some(schema), name)
+            })
             .collect();
 
-        let (tables_and_views, enums) = self.load_tables(table_names).await?;
-        let foreign_keys = self.get_fks(&tables_and_views)?;
-
-        let mat_views_name: Vec<String> = self
+        let mat_views_names: Vec<(String, String)> = self
             .client
-            .query(GET_MATERIALIZED_VIEWS, &[&self.schema_name])
-            .await?
+            .query(GET_MATERIALIZED_VIEWS, &[&self.schema_names])
+            .await
+            .map_err(SqlantError::query(GET_MATERIALIZED_VIEWS))?
             .iter()
-            .map(|row| row.get("matview_name"))
+            .map(|row| (row.get("schema_name"), row.get("matview_name")))
+            .filter(|(schema, name): &(String, String)| {
+                self.table_filter.is_match(Some(schema), name)
+            })
             .collect();
-        let (mat_views, _) = self.load_tables(mat_views_name).await?;
 
-        // Collect table names and types as a vector of tuples
-        let table_names_with_types: Vec<(String, TableType)> = res
-            .iter()
-            .map(|row| (row.get("table_name"), row.get("table_type")))
-            .collect();
+        let mut tables_and_views = vec![];
+        let mut mat_views = vec![];
+        let mut enums = SqlEnums::new();
+        for schema_name in self.schema_names.clone() {
+            // Extract just the table names of schema for loading
+            let table_names: Vec<String> = table_names_with_types
+                .iter()
+                .filter(|(schema, _, _)| schema == &schema_name)
+                .map(|(_, name, _)| name.clone())
+                .collect();
+            let (schema_tables, schema_enums) = self.load_tables(&schema_name, table_names).await?;
+            tables_and_views.extend(schema_tables);
+            enums.extend(schema_enums);
+
+            let mat_views_name: Vec<String> = mat_views_names
+                .iter()
+                .filter(|(schema, _)| schema == &schema_name)
+                .map(|(_, name)| name.clone())
+                .collect();
+            let (schema_mat_views, _) = self.load_tables(&schema_name, mat_views_name).await?;
+            mat_views.extend(schema_mat_views);
+        }
+        // Only columns of loaded FKs are marked
+        let mut foreign_keys = self.get_fks(&tables_and_views)?;
+        mark_foreign_key_columns(&mut tables_and_views, &mut foreign_keys);
 
         let mut views: Vec<Arc<View>> = mat_views
             .into_iter()
@@ -434,7 +952,11 @@ impl SqlERDataLoader for PostgreSqlERDLoader {
                 View {
                     materialized: true,
                     name: v.name,
+                    schema: v.schema,
                     columns: v.columns,
+                    comment: v.comment,
+                    depends_on: vec![],
+                    used_columns: BTreeMap::new(),
                 }
                 .into()
             })
@@ -443,19 +965,32 @@ impl SqlERDataLoader for PostgreSqlERDLoader {
         let mut tables: Vec<Arc<Table>> = vec![];
 
         for entity in tables_and_views.into_iter() {
-            let (_, r#type) = table_names_with_types
+            let (_, _, r#type) = table_names_with_types
                 .iter()
-                .find(|t| t.0 == entity.name)
+                .find(|(schema, name, _)| {
+                    entity.schema.as_ref() == Some(schema)
+                        && entity.name == self.entity_name(schema, name)
+                })
                 .unwrap();
             match r#type {
                 TableType::BaseTable => tables.push(entity),
                 TableType::View => {
-                    let Table { name, columns, .. } = Arc::try_unwrap(entity).unwrap();
+                    let Table {
+                        name,
+                        schema,
+                        columns,
+                        comment,
+                        ..
+                    } = Arc::try_unwrap(entity).unwrap();
                     views.push(
                         View {
                             materialized: false,
                             name,
+                            schema,
                             columns,
+                            comment,
+                            depends_on: vec![],
+                            used_columns: BTreeMap::new(),
                         }
                         .into(),
                     );
@@ -463,6 +998,8 @@ impl SqlERDataLoader for PostgreSqlERDLoader {
             }
         }
 
+        let views = self.load_view_dependencies(&tables, views).await?;
+
         Ok(SqlERData {
             tables,
             foreign_keys,
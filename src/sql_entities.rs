@@ -1,9 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use std::vec::Vec;
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct TableColumn {
     pub name: String,
     pub col_num: i16, // Can be used like id of column
@@ -11,6 +11,7 @@ pub struct TableColumn {
     // Let's keep it as string not ENUM
     pub datatype: String,
     pub constraints: BTreeSet<ColumnConstraints>,
+    pub comment: Option<String>,
 }
 
 impl TableColumn {
@@ -23,17 +24,40 @@ impl TableColumn {
     pub fn is_nn(&self) -> bool {
         self.constraints.contains(&ColumnConstraints::NotNull)
     }
+    pub fn is_unique(&self) -> bool {
+        self.constraints.contains(&ColumnConstraints::Unique)
+    }
+    pub fn is_indexed(&self) -> bool {
+        self.constraints.contains(&ColumnConstraints::Index)
+    }
+    pub fn default_value(&self) -> Option<&str> {
+        self.constraints.iter().find_map(|c| match c {
+            ColumnConstraints::Default(value) => Some(value.as_str()),
+            _ => None,
+        })
+    }
+    // Table CHECK constraints are attached to every column they mention
+    pub fn checks(&self) -> Vec<&str> {
+        self.constraints
+            .iter()
+            .filter_map(|c| match c {
+                ColumnConstraints::Check(expr) => Some(expr.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ColumnConstraints {
     NotNull,
     PrimaryKey,
     ForeignKey,
-    Unique,
-    Check(String), // TODO Ex: CONSTRAINT CHK_Person CHECK (Age>=18 AND City='Sandnes') // +
-    Default(String), // TODO
-    Index,         // Mark NOT Unique indexes
+    Unique,          // Single-column UNIQUE. Columns of multi-column UNIQUE aren't marked
+    Check(String),   // Expression without CHECK keyword. Ex: age >= 18 AND city = 'Sandnes'
+    Default(String), // Default expression. Ex: nextval('customer_id_seq'::regclass)
+    Index,           // Mark NOT Unique indexes
 }
 
 // Types of relationship https://launchschool.com/books/sql_first_edition/read/multi_tables
@@ -44,20 +68,130 @@ pub struct ForeignKey {
     pub target_table: Arc<Table>,
     pub target_columns: Vec<Arc<TableColumn>>,
     pub is_zero_one_to_one: bool, // 0..1 to 1
+    // Name of constraint. None if database doesn't name foreign keys (SQLite)
+    pub name: Option<String>,
+    pub on_delete: ReferentialAction,
+    pub on_update: ReferentialAction,
+    // DEFERRABLE and INITIALLY DEFERRED. Only PostgreSQL can defer constraints
+    pub deferrable: bool,
+    pub initially_deferred: bool,
+    pub match_type: MatchType,
+}
+
+// Action of foreign key on deletion or update of referenced row. Ex: ON DELETE CASCADE
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferentialAction {
+    #[default]
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+impl ReferentialAction {
+    // Ex: SET NULL. Unknown rules are treated as default NO ACTION
+    pub fn from_sql(rule: &str) -> ReferentialAction {
+        match rule.to_uppercase().as_str() {
+            "RESTRICT" => ReferentialAction::Restrict,
+            "CASCADE" => ReferentialAction::Cascade,
+            "SET NULL" => ReferentialAction::SetNull,
+            "SET DEFAULT" => ReferentialAction::SetDefault,
+            _ => ReferentialAction::NoAction,
+        }
+    }
+}
+
+impl std::fmt::Display for ReferentialAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ReferentialAction::NoAction => "NO ACTION",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::SetDefault => "SET DEFAULT",
+        })
+    }
+}
+
+// How multi-column foreign key matches NULL values
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchType {
+    #[default]
+    Simple,
+    Full,
+    Partial,
+}
+
+impl std::fmt::Display for MatchType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            MatchType::Simple => "SIMPLE",
+            MatchType::Full => "FULL",
+            MatchType::Partial => "PARTIAL",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TableIndex {
+    pub name: String,
+    // Column names or expressions. Ex: lower(email)
+    pub columns: Vec<String>,
+    pub unique: bool,
+    pub method: String, // btree, hash, gin, gist ...
+    // WHERE clause of partial index
+    pub predicate: Option<String>,
+    // Non-key columns of INCLUDE clause
+    pub include: Vec<String>,
+}
+
+// Ex: customer_email_idx: UNIQUE btree (lower(email)) INCLUDE (name) WHERE deleted_at IS NULL
+impl std::fmt::Display for TableIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.name)?;
+        if self.unique {
+            write!(f, "UNIQUE ")?;
+        }
+        write!(f, "{} ({})", self.method, self.columns.join(", "))?;
+        if !self.include.is_empty() {
+            write!(f, " INCLUDE ({})", self.include.join(", "))?;
+        }
+        if let Some(predicate) = &self.predicate {
+            write!(f, " WHERE {predicate}")?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Table {
+    // Qualified as `schema.table` if several schemas are loaded
     pub name: String,
+    pub schema: Option<String>,
     pub columns: Vec<Arc<TableColumn>>,
     pub has_composite_pk: bool,
+    pub indexes: Vec<TableIndex>, // Sorted by name
+    pub comment: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct View {
     pub materialized: bool,
+    // Qualified as `schema.view` if several schemas are loaded
     pub name: String,
+    pub schema: Option<String>,
     pub columns: Vec<Arc<TableColumn>>,
+    pub comment: Option<String>,
+    // Names of loaded tables and views that are used by query of view, sorted
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    // Sorted names of columns of `depends_on` entities that are used by query of view.
+    // Entities without resolved columns (e.g. SELECT count(*)) have no entry. PostgreSQL only
+    #[serde(default)]
+    pub used_columns: BTreeMap<String, Vec<String>>,
 }
 
 // key - enum_name (type) v = enum values
@@ -72,6 +206,39 @@ pub struct SqlERData {
     pub views: Vec<Arc<View>>,
 }
 
+impl SqlERData {
+    // Distinct schemas of tables and views. Generators group entities by schema
+    // only if there are several of them.
+    pub fn schemas(&self) -> BTreeSet<&str> {
+        self.tables
+            .iter()
+            .filter_map(|t| t.schema.as_deref())
+            .chain(self.views.iter().filter_map(|v| v.schema.as_deref()))
+            .collect()
+    }
+
+    // Pairs of view and name of table or view it depends on, sorted by names.
+    // Dependencies on entities which aren't present (e.g. filtered out) are skipped.
+    pub fn view_dependencies(&self) -> Vec<(&View, &str)> {
+        let is_present = |name: &str| {
+            self.tables.iter().any(|tbl| tbl.name == name)
+                || self.views.iter().any(|view| view.name == name)
+        };
+        let mut res: Vec<(&View, &str)> = self
+            .views
+            .iter()
+            .flat_map(|view| {
+                view.depends_on
+                    .iter()
+                    .filter(|name| is_present(name))
+                    .map(|name| (view.as_ref(), name.as_str()))
+            })
+            .collect();
+        res.sort_by(|a, b| (&a.0.name, a.1).cmp(&(&b.0.name, b.1)));
+        res
+    }
+}
+
 #[async_trait::async_trait]
 pub trait SqlERDataLoader {
     // Connection string has to be passed in "constructor"
@@ -89,8 +256,55 @@ impl Table {
 
         Table {
             name,
+            schema: None,
             columns,
             has_composite_pk,
+            indexes: vec![],
+            comment: None,
+        }
+    }
+}
+
+// Only source columns of `foreign_keys` have ForeignKey constraint,
+// so columns of FKs that aren't resolved or are dropped (e.g. by filter) aren't marked.
+// Changed tables are created again, so foreign keys are pointed to the new ones.
+pub(crate) fn mark_foreign_key_columns(tables: &mut [Arc<Table>], foreign_keys: &mut [ForeignKey]) {
+    for tbl in tables.iter_mut() {
+        let fk_columns: BTreeSet<String> = foreign_keys
+            .iter()
+            .filter(|fk| fk.source_table.name == tbl.name)
+            .flat_map(|fk| fk.source_columns.iter().map(|col| col.name.clone()))
+            .collect();
+        let is_changed = |col: &TableColumn| col.is_fk() != fk_columns.contains(&col.name);
+        if !tbl.columns.iter().any(|col| is_changed(col)) {
+            continue;
+        }
+        for col in Arc::make_mut(tbl)
+            .columns
+            .iter_mut()
+            .filter(|col| is_changed(col))
+        {
+            let constraints = &mut Arc::make_mut(col).constraints;
+            if !constraints.remove(&ColumnConstraints::ForeignKey) {
+                constraints.insert(ColumnConstraints::ForeignKey);
+            }
+        }
+
+        let repoint = |table: &mut Arc<Table>, columns: &mut Vec<Arc<TableColumn>>| {
+            *table = Arc::clone(tbl);
+            for col in columns.iter_mut() {
+                if let Some(new_col) = tbl.columns.iter().find(|c| c.name == col.name) {
+                    *col = Arc::clone(new_col);
+                }
+            }
+        };
+        for fk in foreign_keys.iter_mut() {
+            if fk.source_table.name == tbl.name {
+                repoint(&mut fk.source_table, &mut fk.source_columns);
+            }
+            if fk.target_table.name == tbl.name {
+                repoint(&mut fk.target_table, &mut fk.target_columns);
+            }
         }
     }
 }
@@ -138,6 +352,59 @@ impl ForeignKey {
             target_table,
             target_columns,
             is_zero_one_to_one,
+            name: None,
+            on_delete: ReferentialAction::default(),
+            on_update: ReferentialAction::default(),
+            deferrable: false,
+            initially_deferred: false,
+            match_type: MatchType::default(),
+        }
+    }
+
+    pub fn with_name(mut self, name: Option<String>) -> ForeignKey {
+        self.name = name;
+        self
+    }
+
+    pub fn with_actions(
+        mut self,
+        on_delete: ReferentialAction,
+        on_update: ReferentialAction,
+    ) -> ForeignKey {
+        self.on_delete = on_delete;
+        self.on_update = on_update;
+        self
+    }
+
+    pub fn with_deferrable(mut self, deferrable: bool, initially_deferred: bool) -> ForeignKey {
+        self.deferrable = deferrable;
+        self.initially_deferred = initially_deferred;
+        self
+    }
+
+    pub fn with_match_type(mut self, match_type: MatchType) -> ForeignKey {
+        self.match_type = match_type;
+        self
+    }
+
+    // Clauses of constraint that differ from defaults.
+    // Ex: ["ON DELETE CASCADE", "DEFERRABLE INITIALLY DEFERRED", "MATCH FULL"]
+    pub fn clauses(&self) -> Vec<String> {
+        let mut res = vec![];
+        if self.on_delete != ReferentialAction::NoAction {
+            res.push(format!("ON DELETE {}", self.on_delete));
+        }
+        if self.on_update != ReferentialAction::NoAction {
+            res.push(format!("ON UPDATE {}", self.on_update));
+        }
+        match (self.deferrable, self.initially_deferred) {
+            (true, true) => res.push("DEFERRABLE INITIALLY DEFERRED".to_string()),
+            (true, false) => res.push("DEFERRABLE".to_string()),
+            _ => {}
+        }
+        if self.match_type != MatchType::Simple {
+            res.push(format!("MATCH {}", self.match_type));
         }
+        res
     }
 }
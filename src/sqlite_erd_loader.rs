@@ -0,0 +1,306 @@
+use rusqlite::{Connection, OpenFlags};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+use crate::{
+    sql_entities::{
+        mark_foreign_key_columns, ColumnConstraints, ForeignKey, ReferentialAction, SqlERData,
+        SqlERDataLoader, SqlEnums, Table, TableColumn, View,
+    },
+    SqlantError,
+};
+
+static GET_ENTITIES_LIST_QUERY: &str = r#"
+SELECT name, type
+FROM sqlite_master
+WHERE type IN ('table', 'view')
+AND name NOT LIKE 'sqlite_%'
+ORDER BY name;
+"#;
+
+/// https://www.sqlite.org/pragma.html#pragma_table_info
+static GET_COLUMNS_QUERY: &str = r#"
+SELECT cid, name, type, "notnull", dflt_value, pk
+FROM pragma_table_info(?1)
+ORDER BY cid;
+"#;
+
+/// https://www.sqlite.org/pragma.html#pragma_foreign_key_list
+static GET_FOREIGN_KEYS_QUERY: &str = r#"
+SELECT id, seq, "table", "from", "to", on_update, on_delete
+FROM pragma_foreign_key_list(?1)
+ORDER BY id, seq;
+"#;
+
+/// https://www.sqlite.org/pragma.html#pragma_index_list
+static GET_INDEXES_QUERY: &str = r#"
+SELECT il.name AS index_name,
+       il."unique" AS is_unique,
+       ii.name AS column_name
+FROM pragma_index_list(?1) il
+JOIN pragma_index_info(il.name) ii
+WHERE il.origin != 'pk'
+ORDER BY il.name, ii.seqno;
+"#;
+
+/// Internal type of Foreign Key. With values that loaded from db
+#[derive(Debug, Default)]
+struct FkInternal {
+    target_table_name: String,
+    source_columns: Vec<String>,
+    // Empty values mean that FK references PK of target table
+    target_columns: Vec<Option<String>>,
+    on_delete: String, // Ex: CASCADE, SET NULL
+    on_update: String,
+}
+
+pub struct SqliteERDLoader {
+    connection: Connection,
+}
+
+impl SqliteERDLoader {
+    /// `path` is a path to database file, e.g. `path/to.db` from `sqlite://path/to.db`
+    pub fn new(path: &str) -> Result<SqliteERDLoader, SqlantError> {
+        let connection = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .map_err(SqlantError::connection)?;
+        Ok(SqliteERDLoader { connection })
+    }
+
+    /// Column names of single column UNIQUE indexes (UNIQUE constraints are indexes in SQLite)
+    /// and columns that are covered by NOT unique indexes
+    fn load_indexes(
+        &self,
+        entity_name: &str,
+    ) -> Result<(BTreeSet<String>, BTreeSet<String>), SqlantError> {
+        let mut stmt = self.connection.prepare(GET_INDEXES_QUERY)?;
+        let rows = stmt.query_map([entity_name], |row| {
+            Ok((
+                row.get::<_, String>("index_name")?,
+                row.get::<_, bool>("is_unique")?,
+                row.get::<_, Option<String>>("column_name")?,
+            ))
+        })?;
+
+        // index_name -> (is_unique, columns)
+        let mut indexes: BTreeMap<String, (bool, Vec<String>)> = BTreeMap::new();
+        for row in rows {
+            let (index_name, is_unique, column_name) = row?;
+            let index = indexes.entry(index_name).or_insert((is_unique, vec![]));
+            // Expression indexes don't have column name
+            if let Some(column_name) = column_name {
+                index.1.push(column_name);
+            }
+        }
+
+        let mut unique = BTreeSet::new();
+        let mut indexed = BTreeSet::new();
+        for (is_unique, columns) in indexes.into_values() {
+            if !is_unique {
+                indexed.extend(columns);
+            } else if columns.len() == 1 {
+                unique.extend(columns);
+            }
+        }
+        Ok((unique, indexed))
+    }
+
+    fn load_fks(&self, table_name: &str) -> Result<Vec<FkInternal>, SqlantError> {
+        let mut stmt = self.connection.prepare(GET_FOREIGN_KEYS_QUERY)?;
+        let rows = stmt.query_map([table_name], |row| {
+            Ok((
+                row.get::<_, i64>("id")?,
+                row.get::<_, String>("table")?,
+                row.get::<_, String>("from")?,
+                row.get::<_, Option<String>>("to")?,
+                row.get::<_, String>("on_delete")?,
+                row.get::<_, String>("on_update")?,
+            ))
+        })?;
+
+        // Composite FKs are returned as several rows with the same id
+        let mut fks: BTreeMap<i64, FkInternal> = BTreeMap::new();
+        for row in rows {
+            let (id, target_table_name, from, to, on_delete, on_update) = row?;
+            let fk = fks.entry(id).or_insert_with(|| FkInternal {
+                target_table_name,
+                on_delete,
+                on_update,
+                ..Default::default()
+            });
+            fk.source_columns.push(from);
+            fk.target_columns.push(to);
+        }
+        Ok(fks.into_values().collect())
+    }
+
+    fn load_columns(&self, entity_name: &str) -> Result<Vec<Arc<TableColumn>>, SqlantError> {
+        let (unique, indexed) = self.load_indexes(entity_name)?;
+        let mut stmt = self.connection.prepare(GET_COLUMNS_QUERY)?;
+        let rows = stmt.query_map([entity_name], |row| {
+            Ok((
+                row.get::<_, i64>("cid")?,
+                row.get::<_, String>("name")?,
+                row.get::<_, String>("type")?,
+                row.get::<_, bool>("notnull")?,
+                row.get::<_, Option<String>>("dflt_value")?,
+                row.get::<_, i64>("pk")?,
+            ))
+        })?;
+
+        let mut columns = vec![];
+        for row in rows {
+            let (cid, name, datatype, not_null, default_value, pk) = row?;
+            let mut constraints = BTreeSet::new();
+            // pk is an index of column in the primary key (starting from 1) or 0
+            if pk > 0 {
+                // The PRIMARY KEY of a table is a combination of NOT NULL and UNIQUE constraint.
+                constraints.insert(ColumnConstraints::PrimaryKey);
+                constraints.insert(ColumnConstraints::NotNull);
+                constraints.insert(ColumnConstraints::Unique);
+            }
+            if not_null {
+                constraints.insert(ColumnConstraints::NotNull);
+            }
+            if unique.contains(&name) {
+                constraints.insert(ColumnConstraints::Unique);
+            }
+            if indexed.contains(&name) {
+                constraints.insert(ColumnConstraints::Index);
+            }
+            if let Some(default_value) = default_value {
+                constraints.insert(ColumnConstraints::Default(default_value));
+            }
+            columns.push(Arc::new(TableColumn {
+                name,
+                // Keep it 1-based like attnum in PostgreSQL
+                col_num: i16::try_from(cid + 1)
+                    .map_err(|e| SqlantError::Internal(e.to_string()))?,
+                datatype: datatype.to_lowercase(),
+                constraints,
+                // SQLite doesn't have comments
+                comment: None,
+            }));
+        }
+        Ok(columns)
+    }
+
+    /// SQLite matches names case-insensitively and allows FKs to tables that don't exist.
+    /// FKs that can't be resolved are skipped.
+    fn get_fks(tables: &[Arc<Table>], fks: &BTreeMap<String, Vec<FkInternal>>) -> Vec<ForeignKey> {
+        let find_table = |name: &str| {
+            tables
+                .iter()
+                .find(|tbl| tbl.name.eq_ignore_ascii_case(name))
+        };
+        let find_column = |tbl: &Table, name: &str| {
+            tbl.columns
+                .iter()
+                .find(|col| col.name.eq_ignore_ascii_case(name))
+                .map(Arc::clone)
+        };
+
+        let mut res = vec![];
+        for (source_table_name, table_fks) in fks {
+            for fk in table_fks {
+                let fk_data = || {
+                    let source_table = find_table(source_table_name)?;
+                    let target_table = find_table(&fk.target_table_name)?;
+                    let source_columns = fk
+                        .source_columns
+                        .iter()
+                        .map(|name| find_column(source_table, name))
+                        .collect::<Option<Vec<_>>>()?;
+
+                    let target_columns = if fk.target_columns.iter().all(Option::is_none) {
+                        // FOREIGN KEY(x) REFERENCES target -- references PK
+                        target_table
+                            .columns
+                            .iter()
+                            .filter(|col| col.is_pk())
+                            .map(Arc::clone)
+                            .collect()
+                    } else {
+                        fk.target_columns
+                            .iter()
+                            .flatten()
+                            .map(|name| find_column(target_table, name))
+                            .collect::<Option<Vec<_>>>()?
+                    };
+
+                    // SQLite parses but ignores DEFERRABLE and MATCH clauses
+                    Some(
+                        ForeignKey::new(
+                            Arc::clone(source_table),
+                            source_columns,
+                            Arc::clone(target_table),
+                            target_columns,
+                        )
+                        .with_actions(
+                            ReferentialAction::from_sql(&fk.on_delete),
+                            ReferentialAction::from_sql(&fk.on_update),
+                        ),
+                    )
+                };
+                res.extend(fk_data());
+            }
+        }
+        res
+    }
+}
+
+#[async_trait::async_trait]
+impl SqlERDataLoader for SqliteERDLoader {
+    async fn load_erd_data(&mut self) -> Result<SqlERData, crate::SqlantError> {
+        let mut stmt = self.connection.prepare(GET_ENTITIES_LIST_QUERY)?;
+        let entities = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>("name")?, row.get::<_, String>("type")?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut tables = vec![];
+        let mut views = vec![];
+        let mut fks: BTreeMap<String, Vec<FkInternal>> = BTreeMap::new();
+        for (name, r#type) in entities {
+            if r#type == "view" {
+                let columns = self.load_columns(&name)?;
+                views.push(Arc::new(View {
+                    // SQLite doesn't have materialized views
+                    materialized: false,
+                    name,
+                    schema: None,
+                    columns,
+                    comment: None,
+                    // Dependencies of views aren't loaded for SQLite
+                    depends_on: vec![],
+                    used_columns: BTreeMap::new(),
+                }));
+                continue;
+            }
+
+            let table_fks = self.load_fks(&name)?;
+            let columns = self.load_columns(&name)?;
+            if !table_fks.is_empty() {
+                fks.insert(name.clone(), table_fks);
+            }
+            tables.push(Arc::new(Table::new(name, columns)));
+        }
+
+        // Only columns of resolved FKs are marked
+        let mut foreign_keys = Self::get_fks(&tables, &fks);
+        mark_foreign_key_columns(&mut tables, &mut foreign_keys);
+
+        Ok(SqlERData {
+            tables,
+            foreign_keys,
+            // SQLite doesn't have enum types
+            enums: SqlEnums::new(),
+            views,
+        })
+    }
+}
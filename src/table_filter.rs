@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use glob::Pattern;
+
+use crate::sql_entities::{mark_foreign_key_columns, SqlERData, SqlERDataLoader};
+use crate::SqlantError;
+
+/// Include/exclude glob patterns (e.g. `django_*`, `*_old`, `billing.*`) of tables and views.
+/// Patterns are matched against the name of table and against `schema.table`.
+/// Table is kept if it matches any include pattern (or there are no include patterns)
+/// and doesn't match any exclude pattern.
+#[derive(Clone, Debug, Default)]
+pub struct TableFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl TableFilter {
+    pub fn new<S: AsRef<str>>(include: &[S], exclude: &[S]) -> Result<TableFilter, SqlantError> {
+        let patterns = |patterns: &[S]| {
+            patterns
+                .iter()
+                .map(|p| Pattern::new(p.as_ref()))
+                .collect::<Result<Vec<Pattern>, _>>()
+        };
+        Ok(TableFilter {
+            include: patterns(include)?,
+            exclude: patterns(exclude)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// `name` is not qualified by schema
+    pub fn is_match(&self, schema: Option<&str>, name: &str) -> bool {
+        let qualified = schema.map(|schema| format!("{schema}.{name}"));
+        let matches =
+            |p: &Pattern| p.matches(name) || qualified.as_deref().is_some_and(|q| p.matches(q));
+        (self.include.is_empty() || self.include.iter().any(matches))
+            && !self.exclude.iter().any(matches)
+    }
+
+    // Entity names are qualified as `schema.table` if several schemas are loaded
+    fn is_entity_match(&self, schema: Option<&str>, name: &str) -> bool {
+        let name = schema
+            .and_then(|schema| name.strip_prefix(&format!("{schema}.")))
+            .unwrap_or(name);
+        self.is_match(schema, name)
+    }
+
+    /// Removes filtered out tables and views, foreign keys and view dependencies from or to them.
+    /// Source columns of removed foreign keys lose ForeignKey constraint
+    pub fn apply(&self, sql_erd: SqlERData) -> SqlERData {
+        if self.is_empty() {
+            return sql_erd;
+        }
+        let SqlERData {
+            tables,
+            foreign_keys,
+            enums,
+            views,
+        } = sql_erd;
+        let mut tables: Vec<_> = tables
+            .into_iter()
+            .filter(|tbl| self.is_entity_match(tbl.schema.as_deref(), &tbl.name))
+            .collect();
+        let mut foreign_keys: Vec<_> = foreign_keys
+            .into_iter()
+            .filter(|fk| {
+                [&fk.source_table, &fk.target_table]
+                    .iter()
+                    .all(|t| tables.iter().any(|tbl| tbl.name == t.name))
+            })
+            .collect();
+        // Columns of dropped FKs aren't FKs anymore
+        mark_foreign_key_columns(&mut tables, &mut foreign_keys);
+        let views: Vec<_> = views
+            .into_iter()
+            .filter(|view| self.is_entity_match(view.schema.as_deref(), &view.name))
+            .collect();
+        let names: Vec<String> = tables
+            .iter()
+            .map(|tbl| tbl.name.clone())
+            .chain(views.iter().map(|view| view.name.clone()))
+            .collect();
+        let views = views
+            .into_iter()
+            .map(|view| {
+                if view.depends_on.iter().all(|name| names.contains(name)) {
+                    return view;
+                }
+                let mut view = Arc::unwrap_or_clone(view);
+                view.depends_on.retain(|name| names.contains(name));
+                view.used_columns.retain(|name, _| names.contains(name));
+                Arc::new(view)
+            })
+            .collect();
+        SqlERData {
+            tables,
+            foreign_keys,
+            enums,
+            views,
+        }
+    }
+}
+
+/// Applies TableFilter to data of loader which can't filter tables by itself
+pub struct FilteredERDLoader<L> {
+    loader: L,
+    table_filter: TableFilter,
+}
+
+impl<L: SqlERDataLoader> FilteredERDLoader<L> {
+    pub fn new(loader: L, table_filter: TableFilter) -> FilteredERDLoader<L> {
+        FilteredERDLoader {
+            loader,
+            table_filter,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<L: SqlERDataLoader + Send> SqlERDataLoader for FilteredERDLoader<L> {
+    async fn load_erd_data(&mut self) -> Result<SqlERData, SqlantError> {
+        let sql_erd = self.loader.load_erd_data().await?;
+        Ok(self.table_filter.apply(sql_erd))
+    }
+}
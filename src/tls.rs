@@ -0,0 +1,293 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use native_tls::{Certificate, Identity, TlsConnector};
+use postgres_native_tls::MakeTlsConnector;
+use strum_macros::{Display, EnumString};
+
+use crate::SqlantError;
+
+/// TLS mode of PostgreSQL connection, the same as `sslmode` of libpq
+#[derive(Clone, Copy, Debug, Default, Display, EnumString, Eq, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum SslMode {
+    /// Don't use TLS
+    Disable,
+    /// Use TLS if server supports it, certificate isn't verified
+    #[default]
+    Prefer,
+    /// Use TLS, certificate isn't verified (verified as in `verify-ca` if root certificate is set)
+    Require,
+    /// Use TLS, certificate must be signed by trusted CA
+    VerifyCa,
+    /// Use TLS, certificate must be signed by trusted CA and match host name
+    VerifyFull,
+}
+
+/// TLS configuration of PostgreSQL connection.
+/// Unset options are taken from connection string parameters
+/// `sslmode`, `sslrootcert`, `sslcert`, `sslkey` and `sslpassword`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TlsConfig {
+    pub mode: Option<SslMode>,
+    /// PEM file with trusted CA certificates, system certificates are used if it isn't set
+    pub root_cert: Option<PathBuf>,
+    /// Client certificate: PKCS#12 (*.p12, *.pfx) file or PEM file (requires `client_key`)
+    pub client_cert: Option<PathBuf>,
+    /// Client private key in PKCS#8 PEM format
+    pub client_key: Option<PathBuf>,
+    /// Password of PKCS#12 client certificate
+    pub client_cert_password: Option<String>,
+}
+
+static TLS_PARAMS: [&str; 5] = ["sslmode", "sslrootcert", "sslcert", "sslkey", "sslpassword"];
+
+fn is_pkcs12(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("p12") || ext.eq_ignore_ascii_case("pfx"))
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, SqlantError> {
+    fs::read(path).map_err(SqlantError::io(path))
+}
+
+// Certificate::from_pem reads only the first certificate of bundle
+fn pem_certificates(pem: &str) -> Result<Vec<Certificate>, SqlantError> {
+    static BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+    static END: &str = "-----END CERTIFICATE-----";
+    let mut res = vec![];
+    let mut rest = pem;
+    while let Some(start) = rest.find(BEGIN) {
+        let Some(len) = rest[start..].find(END) else {
+            break;
+        };
+        let end = start + len + END.len();
+        res.push(Certificate::from_pem(&rest.as_bytes()[start..end])?);
+        rest = &rest[end..];
+    }
+    Ok(res)
+}
+
+// Ex: %2Ftmp%2Fca.crt -> /tmp/ca.crt
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut res = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                res.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                res.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&res).into_owned()
+}
+
+// Parameters of key-value connection string with their positions.
+// Ex: host=localhost sslrootcert='/path with spaces/ca.crt'
+fn key_value_params(connection_string: &str) -> Vec<(String, String, usize, usize)> {
+    let chars: Vec<(usize, char)> = connection_string.char_indices().collect();
+    let position = |i: usize| {
+        chars
+            .get(i)
+            .map_or(connection_string.len(), |(pos, _)| *pos)
+    };
+    let mut res = vec![];
+    let mut i = 0;
+    loop {
+        while chars.get(i).is_some_and(|(_, c)| c.is_whitespace()) {
+            i += 1;
+        }
+        if i >= chars.len() {
+            return res;
+        }
+        let start = i;
+        let mut key = String::new();
+        while let Some((_, c)) = chars
+            .get(i)
+            .filter(|(_, c)| *c != '=' && !c.is_whitespace())
+        {
+            key.push(*c);
+            i += 1;
+        }
+        while chars.get(i).is_some_and(|(_, c)| c.is_whitespace()) {
+            i += 1;
+        }
+        // '='
+        i += 1;
+        while chars.get(i).is_some_and(|(_, c)| c.is_whitespace()) {
+            i += 1;
+        }
+        let mut value = String::new();
+        let is_quoted = chars.get(i).is_some_and(|(_, c)| *c == '\'');
+        if is_quoted {
+            i += 1;
+        }
+        while let Some((_, c)) = chars.get(i) {
+            match c {
+                '\\' => {
+                    if let Some((_, escaped)) = chars.get(i + 1) {
+                        value.push(*escaped);
+                    }
+                    i += 2;
+                    continue;
+                }
+                '\'' if is_quoted => {
+                    i += 1;
+                    break;
+                }
+                c if c.is_whitespace() && !is_quoted => break,
+                c => value.push(*c),
+            }
+            i += 1;
+        }
+        res.push((key, value, position(start), position(i)));
+    }
+}
+
+impl TlsConfig {
+    pub fn is_empty(&self) -> bool {
+        self == &TlsConfig::default()
+    }
+
+    pub fn mode(&self) -> SslMode {
+        self.mode.unwrap_or_default()
+    }
+
+    /// Options which are set in `self` take precedence over `other`
+    pub fn or(self, other: TlsConfig) -> TlsConfig {
+        TlsConfig {
+            mode: self.mode.or(other.mode),
+            root_cert: self.root_cert.or(other.root_cert),
+            client_cert: self.client_cert.or(other.client_cert),
+            client_key: self.client_key.or(other.client_key),
+            client_cert_password: self.client_cert_password.or(other.client_cert_password),
+        }
+    }
+
+    fn set_param(&mut self, key: &str, value: String) -> Result<(), SqlantError> {
+        match key {
+            "sslmode" => {
+                self.mode = Some(SslMode::from_str(&value).map_err(|_| {
+                    SqlantError::UnsupportedOption(format!("sslmode {value} isn't supported"))
+                })?)
+            }
+            "sslrootcert" => self.root_cert = Some(value.into()),
+            "sslcert" => self.client_cert = Some(value.into()),
+            "sslkey" => self.client_key = Some(value.into()),
+            "sslpassword" => self.client_cert_password = Some(value),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Extracts TLS parameters from PostgreSQL connection string (URL or key-value).
+    /// Returns connection string without them, because they aren't supported by tokio-postgres.
+    pub fn from_connection_string(
+        connection_string: &str,
+    ) -> Result<(String, TlsConfig), SqlantError> {
+        let mut tls_config = TlsConfig::default();
+        if connection_string.starts_with("postgres://")
+            || connection_string.starts_with("postgresql://")
+        {
+            let Some((url, query)) = connection_string.split_once('?') else {
+                return Ok((connection_string.to_string(), tls_config));
+            };
+            let mut params = vec![];
+            for param in query.split('&') {
+                match param.split_once('=') {
+                    Some((key, value)) if TLS_PARAMS.contains(&key) => {
+                        tls_config.set_param(key, percent_decode(value))?
+                    }
+                    _ => params.push(param),
+                }
+            }
+            return Ok(if params.is_empty() {
+                (url.to_string(), tls_config)
+            } else {
+                (format!("{url}?{}", params.join("&")), tls_config)
+            });
+        }
+
+        let mut params = vec![];
+        for (key, value, start, end) in key_value_params(connection_string) {
+            if TLS_PARAMS.contains(&key.as_str()) {
+                tls_config.set_param(&key, value)?;
+            } else {
+                params.push(&connection_string[start..end]);
+            }
+        }
+        Ok((params.join(" "), tls_config))
+    }
+
+    pub(crate) fn postgres_ssl_mode(&self) -> tokio_postgres::config::SslMode {
+        match self.mode() {
+            SslMode::Disable => tokio_postgres::config::SslMode::Disable,
+            SslMode::Prefer => tokio_postgres::config::SslMode::Prefer,
+            _ => tokio_postgres::config::SslMode::Require,
+        }
+    }
+
+    fn identity(&self) -> Result<Option<Identity>, SqlantError> {
+        match (&self.client_cert, &self.client_key) {
+            (None, None) => Ok(None),
+            (Some(cert), None) if is_pkcs12(cert) => Ok(Some(Identity::from_pkcs12(
+                &read(cert)?,
+                self.client_cert_password.as_deref().unwrap_or_default(),
+            )?)),
+            (Some(cert), Some(key)) if !is_pkcs12(cert) => {
+                Ok(Some(Identity::from_pkcs8(&read(cert)?, &read(key)?)?))
+            }
+            (Some(_), Some(_)) => Err(SqlantError::UnsupportedOption(
+                "sslkey can't be used with PKCS#12 sslcert".to_string(),
+            )),
+            (Some(_), None) => Err(SqlantError::UnsupportedOption(
+                "sslkey is required for PEM sslcert".to_string(),
+            )),
+            (None, Some(_)) => Err(SqlantError::UnsupportedOption(
+                "sslkey requires sslcert".to_string(),
+            )),
+        }
+    }
+
+    pub fn connector(&self) -> Result<MakeTlsConnector, SqlantError> {
+        let mut builder = TlsConnector::builder();
+        if let Some(root_cert) = &self.root_cert {
+            let pem = read(root_cert)?;
+            let certificates = pem_certificates(&String::from_utf8_lossy(&pem))?;
+            if certificates.is_empty() {
+                builder.add_root_certificate(Certificate::from_der(&pem)?);
+            }
+            for certificate in certificates {
+                builder.add_root_certificate(certificate);
+            }
+            builder.disable_built_in_roots(true);
+        }
+        if let Some(identity) = self.identity()? {
+            builder.identity(identity);
+        }
+        match self.mode() {
+            SslMode::Disable | SslMode::Prefer => {
+                builder.danger_accept_invalid_certs(true);
+            }
+            SslMode::Require if self.root_cert.is_none() => {
+                builder.danger_accept_invalid_certs(true);
+            }
+            SslMode::Require | SslMode::VerifyCa => {
+                builder.danger_accept_invalid_hostnames(true);
+            }
+            SslMode::VerifyFull => {}
+        }
+        Ok(MakeTlsConnector::new(builder.build()?))
+    }
+}
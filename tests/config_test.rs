@@ -0,0 +1,181 @@
+use std::{fs, path::Path, process::Command};
+
+// Runs sqlant in `dir`, returns exit code, stdout and stderr
+fn sqlant(dir: &Path, args: &[&str]) -> (i32, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_sqlant"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+fn config_dir(config: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let test_db = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/test_db.sql");
+    fs::write(
+        dir.path().join("sqlant.toml"),
+        config.replace("TEST_DB", test_db.to_str().unwrap()),
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn defaults() {
+    let dir = config_dir(
+        r#"
+        from_file = ["TEST_DB"]
+        output = "mermaid"
+        include = ["customer", "customer_order"]
+        "#,
+    );
+    let (code, stdout, _) = sqlant(dir.path(), &[]);
+    assert_eq!(code, 0);
+    assert!(stdout.starts_with("erDiagram\n"));
+    assert!(stdout.contains("customer_order {"));
+    assert!(!stdout.contains("sku {"));
+
+    // Command line options override config file
+    let (code, stdout, _) = sqlant(dir.path(), &["-o", "dbml", "--include", "sku"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("Table sku {"));
+    assert!(!stdout.contains("Table customer {"));
+}
+
+#[test]
+fn profiles() {
+    let dir = config_dir(
+        r#"
+        from_file = ["TEST_DB"]
+        not_null = true
+
+        [profiles.orders]
+        focus = ["customer_order"]
+        depth = 1
+        out = ["docs/orders.dot"]
+
+        [profiles.catalog]
+        include = "sku"
+        conceptual = true
+        "#,
+    );
+    let (code, stdout, _) = sqlant(dir.path(), &["--profile", "orders"]);
+    assert_eq!(code, 0);
+    assert!(stdout.is_empty());
+    let dot = fs::read_to_string(dir.path().join("docs/orders.dot")).unwrap();
+    assert!(dot.contains("\"customer_order\" -> \"customer\""));
+    assert!(!dot.contains("\"vendor\""));
+
+    let (code, stdout, _) = sqlant(dir.path(), &["--profile", "catalog"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("table(sku)"));
+    assert!(!stdout.contains("table(product)"));
+
+    let (code, _, stderr) = sqlant(dir.path(), &["--profile", "billing"]);
+    assert_eq!(code, 2);
+    assert_eq!(
+        stderr,
+        "error: Invalid config file: sqlant.toml: profiles.billing: profile is not defined\n"
+    );
+}
+
+#[test]
+fn config_option() {
+    let dir = config_dir("");
+    fs::write(
+        dir.path().join("erd.toml"),
+        "output = \"dot\"\nfrom_json = \"tests/test_db.json\"\n",
+    )
+    .unwrap();
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let (code, stdout, _) = sqlant(
+        Path::new(manifest_dir),
+        &["--config", dir.path().join("erd.toml").to_str().unwrap()],
+    );
+    assert_eq!(code, 0);
+    assert!(stdout.starts_with("digraph sqlant {"));
+
+    // Source of schema on command line replaces source of config file
+    let (code, stdout, _) = sqlant(
+        Path::new(manifest_dir),
+        &[
+            "--config",
+            dir.path().join("erd.toml").to_str().unwrap(),
+            "--from-file",
+            "tests/test_db.sql",
+        ],
+    );
+    assert_eq!(code, 0);
+    assert!(stdout.contains("\"top_customers\""));
+}
+
+#[test]
+fn validation() {
+    for (config, message) in [
+        (
+            "direction = \"up\"",
+            "direction: expected one of tb, bt, lr, rl, got up",
+        ),
+        (
+            "[profiles.docs]\nlegnd = true",
+            "profiles.docs.legnd: unknown option",
+        ),
+        ("depth = \"2\"", "depth: expected non-negative integer"),
+        ("enums = \"yes\"", "enums: expected boolean"),
+        ("schema = [1]", "schema: expected array of strings"),
+        ("profile = \"docs\"", "profile: unknown option"),
+        (
+            "connection_string = \"sqlite://db.sqlite\"\nfrom_file = \"db.sql\"",
+            "connection_string, from_file can't be used together",
+        ),
+    ] {
+        let dir = config_dir(config);
+        let (code, _, stderr) = sqlant(dir.path(), &[]);
+        assert_eq!(code, 2);
+        assert_eq!(
+            stderr,
+            format!("error: Invalid config file: sqlant.toml: {message}\n")
+        );
+    }
+
+    let dir = config_dir("from_file = [\"TEST_DB\"]\nsplit = \"components\"");
+    let (code, _, stderr) = sqlant(dir.path(), &[]);
+    assert_eq!(code, 5);
+    assert!(stderr.contains("--out-dir is required by --split"));
+}
+
+#[test]
+fn options_required_by_command_line() {
+    let dir = config_dir("from_file = [\"TEST_DB\"]\nout_dir = \"erd\"");
+    let (code, _, stderr) = sqlant(dir.path(), &["--split", "components"]);
+    assert_eq!(code, 0, "{stderr}");
+    assert!(dir.path().join("erd/index.md").is_file());
+
+    let (code, _, stderr) = sqlant(dir.path(), &["--split", "groups"]);
+    assert_eq!(code, 5);
+    assert!(stderr.contains("--groups is required by --split groups"));
+
+    let (code, _, stderr) = sqlant(dir.path(), &["--split", "components", "--out", "erd.puml"]);
+    assert_eq!(code, 5);
+    assert!(stderr.contains("--split can't be used with --out"));
+
+    let (code, stdout, _) = sqlant(dir.path(), &[]);
+    assert_eq!(code, 0);
+    assert!(stdout.starts_with("@startuml"));
+
+    let (code, _, stderr) = sqlant(dir.path(), &["--out-dir", "docs"]);
+    assert_eq!(code, 5);
+    assert!(stderr.contains("--split is required by --out-dir"));
+
+    let (code, _, stderr) = sqlant(
+        dir.path(),
+        &["sqlite://db.sqlite", "--from-json", "db.json"],
+    );
+    assert_eq!(code, 5);
+    assert!(stderr.contains("connection string, --from-json can't be used together"));
+}
@@ -0,0 +1,111 @@
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader, get_generator, sql_entities::*, GeneratorConfigOptions,
+    GeneratorType,
+};
+
+async fn generate(sql: &str) -> String {
+    let mut parser = DdlERDLoader::new(sql.to_string(), vec!["public".to_string()]);
+    let sql_er_data: SqlERData = parser.load_erd_data().await.unwrap();
+    get_generator(GeneratorType::Dbml)
+        .unwrap()
+        .generate(sql_er_data, &GeneratorConfigOptions::default())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn tables() {
+    let result = generate(
+        r#"
+        CREATE TYPE status AS ENUM ('active', 'on hold');
+        CREATE TABLE account (
+            id serial PRIMARY KEY,
+            email text NOT NULL UNIQUE,
+            balance numeric DEFAULT 0,
+            status status NOT NULL DEFAULT 'active',
+            created_at timestamptz NOT NULL DEFAULT now()
+        );
+        CREATE INDEX account_lower_email_idx ON account (lower(email));
+        COMMENT ON TABLE account IS 'Customer''s account';
+        COMMENT ON COLUMN account.balance IS 'In cents';
+        "#,
+    )
+    .await;
+    assert_eq!(
+        result,
+        r#"Enum status {
+  active
+  "on hold"
+}
+
+Table account {
+  id integer [pk, default: `nextval('account_id_seq'::regclass)`]
+  email text [not null, unique]
+  balance numeric [default: 0, note: 'In cents']
+  status status [not null, default: `'active'`]
+  created_at "timestamp with time zone" [not null, default: `now()`]
+
+  indexes {
+    (`lower(email)`) [name: 'account_lower_email_idx']
+  }
+
+  Note: 'Customer\'s account'
+}
+
+"#
+    );
+}
+
+#[tokio::test]
+async fn refs() {
+    let result = generate(
+        r#"
+        CREATE TABLE a (id int PRIMARY KEY);
+        CREATE TABLE b (id int PRIMARY KEY REFERENCES a);
+        CREATE TABLE c (a_id int REFERENCES a, b_id int REFERENCES b, PRIMARY KEY (a_id, b_id));
+        CREATE TABLE d (
+            id int PRIMARY KEY,
+            a_id int,
+            b_id int,
+            UNIQUE (a_id, b_id),
+            FOREIGN KEY (a_id, b_id) REFERENCES c (a_id, b_id)
+        );
+        "#,
+    )
+    .await;
+    assert!(result.contains("Ref: b.id - a.id\n"));
+    assert!(result.contains("Ref: c.a_id > a.id\n"));
+    assert!(result.contains("Ref: d.(a_id, b_id) > c.(a_id, b_id)\n"));
+    assert!(result.contains(
+        "Table c {\n  a_id integer [not null]\n  b_id integer [not null]\n\n  indexes {\n    (a_id, b_id) [pk]\n  }\n}\n"
+    ));
+    assert!(result.contains(
+        "  a_id integer\n  b_id integer\n\n  indexes {\n    (a_id, b_id) [name: 'd_a_id_b_id_key', unique]\n  }\n"
+    ));
+}
+
+#[tokio::test]
+async fn schemas() {
+    let mut parser = DdlERDLoader::new(
+        r#"
+        CREATE TYPE billing.status AS ENUM ('paid');
+        CREATE TABLE public.customer (id int PRIMARY KEY);
+        CREATE TABLE billing.invoice (
+            id int PRIMARY KEY,
+            customer_id int REFERENCES public.customer,
+            status billing.status
+        );
+        "#
+        .to_string(),
+        vec!["public".to_string(), "billing".to_string()],
+    );
+    let sql_er_data: SqlERData = parser.load_erd_data().await.unwrap();
+    let result = get_generator(GeneratorType::Dbml)
+        .unwrap()
+        .generate(sql_er_data, &GeneratorConfigOptions::default())
+        .unwrap();
+    // Column type refers to the enum of schema
+    assert!(result.contains("Enum billing.status {\n  paid\n}\n"));
+    assert!(result.contains("Table billing.invoice {\n"));
+    assert!(result.contains("  status billing.status\n"));
+    assert!(result.contains("Ref: billing.invoice.customer_id > public.customer.id\n"));
+}
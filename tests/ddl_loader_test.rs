@@ -0,0 +1,738 @@
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader, sql_entities::ColumnConstraints::*, sql_entities::*, SqlantError,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+mod utils;
+use crate::utils::check_fk;
+
+async fn load_erd(files: &[&str], schema_name: &str) -> SqlERData {
+    let mut parser = DdlERDLoader::from_files(files, vec![schema_name.to_string()]).unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+async fn load_erd_from_sql(sql: &str) -> SqlERData {
+    let mut parser = DdlERDLoader::new(sql.to_string(), vec!["public".to_string()]);
+    parser.load_erd_data().await.unwrap()
+}
+
+fn find_column(sql_er_data: &SqlERData, table_name: &str, column_name: &str) -> Arc<TableColumn> {
+    let table = sql_er_data
+        .tables
+        .iter()
+        .find(|&t| t.name == table_name)
+        .unwrap();
+    Arc::clone(
+        table
+            .columns
+            .iter()
+            .find(|col| col.name == column_name)
+            .unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn enums() {
+    let sql_er_data = load_erd(&["tests/test_db.sql"], "public").await;
+    let expected = BTreeMap::from([(
+        "product_category".to_string(),
+        vec![
+            "electronics".to_string(),
+            "jewelry".to_string(),
+            "home".to_string(),
+        ],
+    )]);
+    assert_eq!(sql_er_data.enums, expected);
+}
+
+#[tokio::test]
+async fn views() {
+    let sql_er_data = load_erd(&["tests/test_db.sql"], "public").await;
+    let column = |name: &str, col_num: i16, datatype: &str| {
+        Arc::new(TableColumn {
+            name: name.to_string(),
+            col_num,
+            datatype: datatype.to_string(),
+            constraints: BTreeSet::new(),
+            comment: None,
+        })
+    };
+    let expected = vec![
+        Arc::new(View {
+            materialized: true,
+            name: "monthly_sales_summary".to_string(),
+            schema: Some("public".to_string()),
+            comment: None,
+            columns: vec![
+                column("month", 1, "date"),
+                column("total_orders", 2, "bigint"),
+                column("total_spent", 3, "numeric"),
+            ],
+            depends_on: vec!["customer_order".to_string()],
+            used_columns: BTreeMap::new(),
+        }),
+        Arc::new(View {
+            materialized: false,
+            name: "top_customers".to_string(),
+            schema: Some("public".to_string()),
+            comment: None,
+            columns: vec![
+                column("customer_id", 1, "bigint"),
+                column("total_orders", 2, "bigint"),
+                column("total_spent", 3, "numeric"),
+            ],
+            depends_on: vec!["customer_order".to_string()],
+            used_columns: BTreeMap::new(),
+        }),
+    ];
+    assert_eq!(sql_er_data.views, expected);
+}
+
+#[tokio::test]
+async fn view_dependencies() {
+    let sql_er_data = load_erd_from_sql(
+        r#"
+        CREATE TABLE customer (id int PRIMARY KEY, name text);
+        CREATE TABLE customer_order (id int PRIMARY KEY, customer_id int REFERENCES customer);
+        CREATE VIEW order_count AS
+        WITH recent AS (SELECT * FROM customer_order WHERE id > 100)
+        SELECT c.name, (SELECT COUNT(*) FROM recent r WHERE r.customer_id = c.id) AS orders
+        FROM public.customer c;
+        CREATE MATERIALIZED VIEW top_customer AS SELECT * FROM order_count, missing LIMIT 1;
+        "#,
+    )
+    .await;
+    let depends_on = |name: &str| {
+        sql_er_data
+            .views
+            .iter()
+            .find(|v| v.name == name)
+            .unwrap()
+            .depends_on
+            .clone()
+    };
+    // CTE and tables that aren't created are skipped
+    assert_eq!(
+        depends_on("order_count"),
+        vec!["customer", "customer_order"]
+    );
+    assert_eq!(depends_on("top_customer"), vec!["order_count"]);
+}
+
+#[tokio::test]
+async fn columns() {
+    let sql_er_data = load_erd(&["tests/test_db.sql"], "public").await;
+    let tables = BTreeMap::from([
+        (
+            "order_detail_approval",
+            vec![
+                (
+                    "customer_order_id",
+                    "bigint",
+                    vec![PrimaryKey, ForeignKey, NotNull, Unique],
+                ),
+                (
+                    "order_detail_id",
+                    "bigint",
+                    vec![PrimaryKey, ForeignKey, NotNull, Unique],
+                ),
+                ("operator_id", "bigint", vec![NotNull]),
+                ("approved_at", "timestamp with time zone", vec![NotNull]),
+            ],
+        ),
+        (
+            "product",
+            vec![
+                (
+                    "id",
+                    "bigint",
+                    vec![
+                        PrimaryKey,
+                        NotNull,
+                        Unique,
+                        Default("nextval('product_id_seq'::regclass)".into()),
+                    ],
+                ),
+                ("vendor_id", "bigint", vec![ForeignKey, NotNull]),
+                ("name", "text", vec![NotNull, Index]),
+                ("country", "text", vec![NotNull]),
+                ("category", "product_category", vec![NotNull]),
+            ],
+        ),
+    ]);
+
+    for (table_name, cols) in tables {
+        let table = sql_er_data
+            .tables
+            .iter()
+            .find(|&t| t.name == table_name)
+            .unwrap();
+        assert_eq!(cols.len(), table.columns.len());
+        for (exp_col_name, exp_col_type, exp_constraints) in cols {
+            let col = find_column(&sql_er_data, table_name, exp_col_name);
+            assert_eq!(col.constraints, exp_constraints.into_iter().collect());
+            assert_eq!(col.datatype, exp_col_type);
+        }
+    }
+}
+
+#[tokio::test]
+async fn fks() {
+    let sql_er_data = load_erd(&["tests/test_db.sql"], "public").await;
+    check_fk(
+        &sql_er_data,
+        "order_detail_approval",
+        "order_detail",
+        vec!["order_detail_id", "customer_order_id"],
+        vec!["id", "customer_order_id"],
+    );
+    check_fk(
+        &sql_er_data,
+        "order_detail",
+        "customer_order",
+        vec!["customer_order_id"],
+        vec!["id"],
+    );
+    check_fk(
+        &sql_er_data,
+        "vendor_address",
+        "vendor",
+        vec!["vendor_id"],
+        vec!["id"],
+    );
+}
+
+#[tokio::test]
+async fn fk_names() {
+    let sql_er_data = load_erd_from_sql(
+        r#"
+        CREATE TABLE a (id int PRIMARY KEY, code text UNIQUE);
+        CREATE TABLE b (
+            id int PRIMARY KEY,
+            a_id int REFERENCES a,
+            a_code text CONSTRAINT b_code_fk REFERENCES a (code),
+            FOREIGN KEY (id, a_id) REFERENCES a (id, id)
+        );
+        ALTER TABLE a ADD CONSTRAINT a_self_fk FOREIGN KEY (id) REFERENCES a (id);
+        "#,
+    )
+    .await;
+    let mut names: Vec<&str> = sql_er_data
+        .foreign_keys
+        .iter()
+        .map(|fk| fk.name.as_deref().unwrap())
+        .collect();
+    names.sort();
+    // Unnamed FKs are named like in PostgreSQL
+    assert_eq!(
+        names,
+        vec!["a_self_fk", "b_a_id_fkey", "b_code_fk", "b_id_a_id_fkey"]
+    );
+}
+
+#[tokio::test]
+async fn fk_actions() {
+    let sql_er_data = load_erd_from_sql(
+        r#"
+        CREATE TABLE a (id int PRIMARY KEY);
+        CREATE TABLE b (
+            id int PRIMARY KEY,
+            a_id int REFERENCES a ON DELETE SET NULL ON UPDATE RESTRICT,
+            FOREIGN KEY (id) REFERENCES a (id) ON DELETE SET DEFAULT DEFERRABLE
+        );
+        ALTER TABLE a ADD CONSTRAINT a_self_fk FOREIGN KEY (id) REFERENCES a (id)
+            INITIALLY DEFERRED;
+        "#,
+    )
+    .await;
+    let fk = |name: &str| {
+        sql_er_data
+            .foreign_keys
+            .iter()
+            .find(|fk| fk.name.as_deref() == Some(name))
+            .unwrap()
+    };
+    assert_eq!(
+        fk("b_a_id_fkey").clauses(),
+        vec!["ON DELETE SET NULL", "ON UPDATE RESTRICT"]
+    );
+    assert_eq!(
+        fk("b_id_fkey").clauses(),
+        vec!["ON DELETE SET DEFAULT", "DEFERRABLE"]
+    );
+    // INITIALLY DEFERRED implies DEFERRABLE
+    let self_fk = fk("a_self_fk");
+    assert!(self_fk.deferrable && self_fk.initially_deferred);
+    assert_eq!(self_fk.on_delete, ReferentialAction::NoAction);
+}
+
+#[tokio::test]
+async fn tables_data() {
+    let sql_er_data = load_erd(&["tests/test_db.sql"], "public").await;
+    assert_eq!(sql_er_data.tables.len(), 8);
+    assert_eq!(sql_er_data.foreign_keys.len(), 7);
+    for fk in &sql_er_data.foreign_keys {
+        assert_eq!(
+            fk.is_zero_one_to_one,
+            fk.source_table.name == "vendor_address"
+                || fk.source_table.name == "order_detail_approval"
+        );
+    }
+    for tbl in &sql_er_data.tables {
+        assert_eq!(
+            tbl.has_composite_pk,
+            tbl.name == "order_detail_approval" || tbl.name == "order_detail"
+        );
+    }
+}
+
+#[tokio::test]
+async fn custom_schema() {
+    let sql_er_data = load_erd(&["tests/test_schema.sql"], "test_schema").await;
+    assert_eq!(sql_er_data.tables.len(), 2);
+    assert_eq!(sql_er_data.foreign_keys.len(), 2);
+    check_fk(
+        &sql_er_data,
+        "orders",
+        "customers",
+        vec!["customer_id"],
+        vec!["customer_id"],
+    );
+    let col = find_column(&sql_er_data, "customers", "customer_name");
+    assert_eq!(col.datatype, "character varying");
+    assert_eq!(col.constraints, BTreeSet::from([Unique]));
+
+    // Objects of other schemas are skipped
+    let sql_er_data = load_erd(&["tests/test_schema.sql"], "public").await;
+    assert!(sql_er_data.tables.is_empty());
+}
+
+#[tokio::test]
+async fn constraints() {
+    let sql_er_data = load_erd_from_sql(
+        r#"
+        CREATE TABLE account (
+            id serial PRIMARY KEY,
+            email varchar(255) NOT NULL UNIQUE,
+            balance numeric DEFAULT 0 CHECK (balance >= 0),
+            created_at timestamptz NOT NULL DEFAULT now(),
+            starts_at date,
+            ends_at date,
+            CONSTRAINT period CHECK (starts_at < ends_at)
+        );
+        CREATE TABLE payment (
+            id bigserial,
+            account_id integer,
+            amount numeric
+        );
+        ALTER TABLE ONLY payment ADD CONSTRAINT payment_pkey PRIMARY KEY (id);
+        ALTER TABLE ONLY payment
+            ADD CONSTRAINT payment_account_fk FOREIGN KEY (account_id) REFERENCES account (id);
+        ALTER TABLE payment ALTER COLUMN amount SET NOT NULL;
+        CREATE INDEX payment_account_idx ON payment (account_id);
+        CREATE UNIQUE INDEX account_period_idx ON account USING btree (starts_at, ends_at);
+        CREATE INDEX account_lower_email_idx ON account (lower(email));
+        "#,
+    )
+    .await;
+
+    let col = |table_name, column_name| find_column(&sql_er_data, table_name, column_name);
+    assert_eq!(col("account", "id").datatype, "integer");
+    assert_eq!(
+        col("account", "email").constraints,
+        BTreeSet::from([NotNull, Unique])
+    );
+    assert_eq!(
+        col("account", "balance").constraints,
+        BTreeSet::from([Default("0".into()), Check("balance >= 0".into())])
+    );
+    assert_eq!(
+        col("account", "created_at").constraints,
+        BTreeSet::from([NotNull, Default("now()".into())])
+    );
+    assert_eq!(
+        col("account", "created_at").datatype,
+        "timestamp with time zone"
+    );
+    assert_eq!(
+        col("account", "ends_at").constraints,
+        BTreeSet::from([Check("starts_at < ends_at".into())])
+    );
+    assert_eq!(col("payment", "id").datatype, "bigint");
+    assert_eq!(
+        col("payment", "id").constraints,
+        BTreeSet::from([
+            PrimaryKey,
+            NotNull,
+            Unique,
+            Default("nextval('payment_id_seq'::regclass)".into())
+        ])
+    );
+    assert_eq!(
+        col("payment", "account_id").constraints,
+        BTreeSet::from([ForeignKey, Index])
+    );
+    assert_eq!(
+        col("payment", "amount").constraints,
+        BTreeSet::from([NotNull])
+    );
+    check_fk(
+        &sql_er_data,
+        "payment",
+        "account",
+        vec!["account_id"],
+        vec!["id"],
+    );
+
+    let indexes = |table_name: &str| {
+        sql_er_data
+            .tables
+            .iter()
+            .find(|t| t.name == table_name)
+            .unwrap()
+            .indexes
+            .iter()
+            .map(|i| (i.name.clone(), i.columns.clone(), i.unique))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        indexes("account"),
+        vec![
+            ("account_email_key".into(), vec!["email".into()], true),
+            (
+                "account_lower_email_idx".into(),
+                vec!["lower(email)".into()],
+                false
+            ),
+            (
+                "account_period_idx".into(),
+                vec!["starts_at".into(), "ends_at".into()],
+                true
+            ),
+            ("account_pkey".into(), vec!["id".into()], true),
+        ]
+    );
+    assert_eq!(
+        indexes("payment"),
+        vec![
+            (
+                "payment_account_idx".into(),
+                vec!["account_id".into()],
+                false
+            ),
+            ("payment_pkey".into(), vec!["id".into()], true),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn alter_column() {
+    let sql_er_data = load_erd_from_sql(
+        r#"
+        CREATE TABLE account (
+            id int PRIMARY KEY,
+            status text NOT NULL DEFAULT 'new',
+            balance numeric DEFAULT 0,
+            note text NOT NULL
+        );
+        ALTER TABLE account ALTER COLUMN status SET DEFAULT 'active';
+        ALTER TABLE account ALTER COLUMN balance DROP DEFAULT;
+        ALTER TABLE account ALTER COLUMN note DROP NOT NULL;
+        "#,
+    )
+    .await;
+    let col = |column_name| find_column(&sql_er_data, "account", column_name);
+    assert_eq!(
+        col("status").constraints,
+        BTreeSet::from([NotNull, Default("'active'".into())])
+    );
+    assert_eq!(col("balance").constraints, BTreeSet::new());
+    assert_eq!(col("note").constraints, BTreeSet::new());
+}
+
+#[tokio::test]
+async fn expressions() {
+    let sql_er_data = load_erd_from_sql(
+        r#"
+        CREATE TYPE public.status AS ENUM ('new', 'active');
+        CREATE TABLE public.account (
+            status public.status DEFAULT 'new'::public.status,
+            note character varying(10) DEFAULT ('none'::VARCHAR),
+            balance numeric,
+            CONSTRAINT account_balance_check CHECK (((balance)::NUMERIC >= 0))
+        );
+        "#,
+    )
+    .await;
+    let col = |column_name| find_column(&sql_er_data, "account", column_name);
+    assert_eq!(
+        col("status").constraints,
+        BTreeSet::from([Default("'new'::status".into())])
+    );
+    assert_eq!(
+        col("note").constraints,
+        BTreeSet::from([Default("'none'::character varying".into())])
+    );
+    assert_eq!(
+        col("balance").constraints,
+        BTreeSet::from([Check("(balance)::numeric >= 0".into())])
+    );
+}
+
+#[tokio::test]
+async fn unquoted_names_are_folded() {
+    let sql_er_data = load_erd_from_sql(
+        r#"
+        CREATE TABLE Customer (Id int PRIMARY KEY, "FullName" text);
+        CREATE TABLE orders (
+            ID int,
+            Customer_Id int REFERENCES customer(id),
+            CONSTRAINT Orders_PK PRIMARY KEY (id)
+        );
+        CREATE INDEX Orders_Customer_IDX ON ORDERS (CUSTOMER_ID);
+        "#,
+    )
+    .await;
+    let table_names: Vec<&str> = sql_er_data.tables.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(table_names, vec!["customer", "orders"]);
+    find_column(&sql_er_data, "customer", "FullName");
+    check_fk(
+        &sql_er_data,
+        "orders",
+        "customer",
+        vec!["customer_id"],
+        vec!["id"],
+    );
+    assert_eq!(
+        find_column(&sql_er_data, "orders", "id").constraints,
+        BTreeSet::from([PrimaryKey, NotNull, Unique])
+    );
+    let index_names: Vec<&str> = sql_er_data.tables[1]
+        .indexes
+        .iter()
+        .map(|i| i.name.as_str())
+        .collect();
+    assert_eq!(index_names, vec!["orders_customer_idx", "orders_pk"]);
+    assert_eq!(
+        sql_er_data.tables[1].indexes[0].columns,
+        vec!["customer_id".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn create_table_if_not_exists() {
+    let sql_er_data = load_erd_from_sql(
+        r#"
+        CREATE TABLE account (id int PRIMARY KEY);
+        ALTER TABLE account ADD COLUMN email text NOT NULL;
+        CREATE TABLE IF NOT EXISTS account (id int, name text, UNIQUE (name));
+        CREATE TABLE IF NOT EXISTS payment (id int PRIMARY KEY);
+        "#,
+    )
+    .await;
+    let account = &sql_er_data.tables[0];
+    let columns: Vec<&str> = account.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(columns, vec!["email", "id"]);
+    assert_eq!(account.indexes.len(), 1);
+    assert_eq!(sql_er_data.tables[1].name, "payment");
+}
+
+#[tokio::test]
+async fn repeated_loads() {
+    let mut parser =
+        DdlERDLoader::from_files(&["tests/test_db.sql"], vec!["public".to_string()]).unwrap();
+    let first = parser.load_erd_data().await.unwrap();
+    let second = parser.load_erd_data().await.unwrap();
+    assert_eq!(first.foreign_keys.len(), second.foreign_keys.len());
+    let indexes = |sql_er_data: &SqlERData| {
+        sql_er_data
+            .tables
+            .iter()
+            .map(|t| t.indexes.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(indexes(&first), indexes(&second));
+}
+
+#[tokio::test]
+async fn unsupported_statements_are_skipped() {
+    let sql_er_data = load_erd_from_sql(
+        r#"
+        \restrict abc
+        SET statement_timeout = 0;
+        CREATE FUNCTION f() RETURNS trigger AS $$ BEGIN RETURN NEW; END; $$ LANGUAGE plpgsql;
+        CREATE TABLE a (id int PRIMARY KEY);
+        CREATE TABLE b (id int PRIMARY KEY, a_id int REFERENCES a);
+        CREATE TABLE c (id int PRIMARY KEY);
+        CREATE TABLE d (id int, c_id int REFERENCES c);
+        DROP TABLE c CASCADE;
+        "#,
+    )
+    .await;
+    assert_eq!(sql_er_data.tables.len(), 3);
+    assert_eq!(sql_er_data.foreign_keys.len(), 1);
+    check_fk(&sql_er_data, "b", "a", vec!["a_id"], vec!["id"]);
+}
+
+#[tokio::test]
+async fn indexes() {
+    let sql_er_data = load_erd_from_sql(
+        r#"
+        CREATE TABLE document (id int, title text, body jsonb, deleted boolean);
+        CREATE INDEX document_body_idx ON document USING GIN (body);
+        CREATE INDEX ON document (title) INCLUDE (id) WHERE NOT deleted;
+        CREATE INDEX obsolete_idx ON document (id);
+        DROP INDEX obsolete_idx;
+        "#,
+    )
+    .await;
+    let table = &sql_er_data.tables[0];
+    assert_eq!(
+        table.indexes,
+        vec![
+            TableIndex {
+                name: "document_body_idx".into(),
+                columns: vec!["body".into()],
+                unique: false,
+                method: "gin".into(),
+                predicate: None,
+                include: vec![],
+            },
+            TableIndex {
+                name: "document_title_idx".into(),
+                columns: vec!["title".into()],
+                unique: false,
+                method: "btree".into(),
+                predicate: Some("NOT deleted".into()),
+                include: vec!["id".into()],
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn comments() {
+    let sql_er_data = load_erd(&["tests/test_db.sql"], "public").await;
+    let customer = sql_er_data
+        .tables
+        .iter()
+        .find(|t| t.name == "customer")
+        .unwrap();
+    assert_eq!(customer.comment.as_deref(), Some("Customer Information"));
+    assert_eq!(
+        find_column(&sql_er_data, "customer", "name")
+            .comment
+            .as_deref(),
+        Some("Customer Name")
+    );
+    assert_eq!(find_column(&sql_er_data, "customer", "id").comment, None);
+
+    let sql_er_data = load_erd_from_sql(
+        r#"
+        CREATE TABLE note (id int, body text);
+        COMMENT ON TABLE public.note IS 'User notes';
+        COMMENT ON COLUMN note.body IS 'Markdown text';
+        COMMENT ON COLUMN note.id IS 'Obsolete';
+        COMMENT ON COLUMN note.id IS NULL;
+        "#,
+    )
+    .await;
+    assert_eq!(sql_er_data.tables[0].comment.as_deref(), Some("User notes"));
+    assert_eq!(
+        find_column(&sql_er_data, "note", "body").comment.as_deref(),
+        Some("Markdown text")
+    );
+    assert_eq!(find_column(&sql_er_data, "note", "id").comment, None);
+}
+
+#[tokio::test]
+async fn errors() {
+    let mut parser = DdlERDLoader::new(
+        "CREATE TABLE a (id int PRIMARY KEY, b_id int REFERENCES b (id));".to_string(),
+        vec!["public".to_string()],
+    );
+    let err = parser.load_erd_data().await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Foreign key a(b_id) references b(id) which doesn't exist"
+    );
+    assert!(matches!(err, SqlantError::DanglingForeignKey { .. }));
+
+    for (sql, input) in [
+        (
+            "CREATE UNLOGGED TABLE a (id int PRIMARY KEY,);",
+            "CREATE TABLE statement",
+        ),
+        (
+            "CREATE TABLE a (id int); ALTER TABLE a ADD COLUMN;",
+            "ALTER TABLE statement",
+        ),
+    ] {
+        let mut parser = DdlERDLoader::new(sql.to_string(), vec!["public".to_string()]);
+        match parser.load_erd_data().await {
+            Err(SqlantError::Parse { input: actual, .. }) => assert_eq!(actual, input),
+            _ => panic!("Parse error is expected"),
+        }
+    }
+
+    match DdlERDLoader::from_files(&["tests/nonexistent.sql"], vec!["public".to_string()]) {
+        Err(SqlantError::Io { path, .. }) => {
+            assert_eq!(path.to_str(), Some("tests/nonexistent.sql"))
+        }
+        _ => panic!("Io error is expected"),
+    }
+}
+
+#[tokio::test]
+async fn pg_dump_file() {
+    // Generated by `pg_dump --schema-only` from database created by tests/test_db.sql
+    let sql_er_data = load_erd(&["tests/test_db_pg_dump.sql"], "public").await;
+    let col = |table_name, column_name| find_column(&sql_er_data, table_name, column_name);
+    assert_eq!(
+        col("customer", "id").constraints,
+        BTreeSet::from([
+            PrimaryKey,
+            NotNull,
+            Unique,
+            Default("nextval('customer_id_seq'::regclass)".into())
+        ])
+    );
+    assert_eq!(
+        col("order_detail", "amount").constraints,
+        BTreeSet::from([NotNull, Check("amount > 0".into())])
+    );
+    let customer_order = sql_er_data
+        .tables
+        .iter()
+        .find(|t| t.name == "customer_order")
+        .unwrap();
+    let index = customer_order
+        .indexes
+        .iter()
+        .find(|i| i.name == "customer_order_ordered_at_idx")
+        .unwrap();
+    assert_eq!(
+        index.predicate.as_deref(),
+        Some("payment_method = 'card'::text")
+    );
+
+    // Tables are the same as in the hand-written DDL
+    let hand_written = load_erd(&["tests/test_db.sql"], "public").await;
+    let tables = |sql_er_data: &SqlERData| {
+        sql_er_data
+            .tables
+            .iter()
+            .map(|t| {
+                let indexes: Vec<String> = t.indexes.iter().map(|i| i.name.clone()).collect();
+                (t.name.clone(), t.columns.clone(), indexes)
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(tables(&sql_er_data), tables(&hand_written));
+    assert_eq!(sql_er_data.enums, hand_written.enums);
+    assert_eq!(
+        sql_er_data.foreign_keys.len(),
+        hand_written.foreign_keys.len()
+    );
+}
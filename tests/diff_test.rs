@@ -0,0 +1,286 @@
+use std::env;
+
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader,
+    diff::{Change, SqlERDiff},
+    get_generator, lookup_source_loader,
+    sql_entities::ColumnConstraints::*,
+    sql_entities::*,
+    table_filter::TableFilter,
+    tls::TlsConfig,
+    GeneratorConfigOptions, GeneratorType,
+};
+
+static OLD_SQL: &str = r#"
+    CREATE TYPE status AS ENUM ('active', 'blocked');
+    CREATE TYPE color AS ENUM ('red');
+    CREATE TABLE account (id int PRIMARY KEY, email text, fax text, status status);
+    CREATE TABLE legacy (id int PRIMARY KEY, account_id int REFERENCES account);
+    CREATE VIEW active_account AS SELECT id FROM account;
+"#;
+
+static NEW_SQL: &str = r#"
+    CREATE TYPE status AS ENUM ('active', 'deleted');
+    CREATE TABLE account (
+        id int PRIMARY KEY,
+        email varchar(100) NOT NULL,
+        status status,
+        created_at timestamptz DEFAULT now()
+    );
+    CREATE TABLE payment (id int PRIMARY KEY, account_id int REFERENCES account);
+    CREATE VIEW active_account AS SELECT id FROM account;
+    CREATE VIEW payment_account AS SELECT account_id FROM payment;
+"#;
+
+async fn load_erd(sql: &str) -> SqlERData {
+    let mut parser = DdlERDLoader::new(sql.to_string(), vec!["public".to_string()]);
+    parser.load_erd_data().await.unwrap()
+}
+
+async fn load_diff() -> (SqlERData, SqlERDiff) {
+    let (old, new) = (load_erd(OLD_SQL).await, load_erd(NEW_SQL).await);
+    let diff = SqlERDiff::new(&old, &new);
+    (diff.merge(&old, &new), diff)
+}
+
+async fn generate(generator_type: GeneratorType) -> String {
+    let (sql_er_data, diff) = load_diff().await;
+    get_generator(generator_type)
+        .unwrap()
+        .generate(
+            sql_er_data,
+            &GeneratorConfigOptions {
+                draw_enums: true,
+                diff: Some(diff),
+                ..GeneratorConfigOptions::default()
+            },
+        )
+        .unwrap()
+}
+
+#[tokio::test]
+async fn changes() {
+    let (_, diff) = load_diff().await;
+    assert_eq!(diff.table_change("account"), Some(Change::Changed));
+    assert_eq!(diff.table_change("legacy"), Some(Change::Removed));
+    assert_eq!(diff.table_change("payment"), Some(Change::Added));
+    assert_eq!(diff.column_change("account", "id"), None);
+    assert_eq!(diff.column_change("account", "fax"), Some(Change::Removed));
+    assert_eq!(
+        diff.column_change("account", "created_at"),
+        Some(Change::Added)
+    );
+    let email = &diff.tables["account"].columns["email"];
+    assert_eq!(email.change, Change::Changed);
+    assert_eq!(email.old_datatype.as_deref(), Some("text"));
+    assert_eq!(email.new_datatype.as_deref(), Some("character varying"));
+    assert_eq!(email.added_constraints, vec![NotNull]);
+    assert!(email.removed_constraints.is_empty());
+    assert_eq!(diff.enum_change("status"), Some(Change::Changed));
+    assert_eq!(diff.enum_change("color"), Some(Change::Removed));
+    assert_eq!(diff.view_change("active_account"), None);
+    assert_eq!(diff.view_change("payment_account"), Some(Change::Added));
+    assert_eq!(
+        diff.foreign_keys
+            .iter()
+            .map(|(name, diff)| (name.as_str(), diff.change))
+            .collect::<Vec<_>>(),
+        vec![
+            ("legacy(account_id) -> account(id)", Change::Removed),
+            ("payment(account_id) -> account(id)", Change::Added),
+        ]
+    );
+
+    let sql_er_data = load_erd(OLD_SQL).await;
+    assert!(SqlERDiff::new(&sql_er_data, &sql_er_data).is_empty());
+}
+
+#[tokio::test]
+async fn merge() {
+    let (sql_er_data, _) = load_diff().await;
+    let table_names: Vec<&str> = sql_er_data.tables.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(table_names, vec!["account", "payment", "legacy"]);
+    let account_columns: Vec<&str> = sql_er_data.tables[0]
+        .columns
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect();
+    assert_eq!(
+        account_columns,
+        vec!["created_at", "email", "id", "status", "fax"]
+    );
+    assert_eq!(sql_er_data.foreign_keys.len(), 2);
+    assert_eq!(
+        sql_er_data.enums.keys().collect::<Vec<_>>(),
+        vec!["color", "status"]
+    );
+    assert_eq!(sql_er_data.views.len(), 2);
+}
+
+#[tokio::test]
+async fn summary() {
+    let (_, diff) = load_diff().await;
+    assert_eq!(
+        diff.summary(),
+        r#"## Tables
+- changed `account`
+  - added column `created_at`
+  - changed column `email`: type `text` -> `character varying`, added NOT NULL
+  - removed column `fax`
+- removed `legacy`
+- added `payment`
+## Foreign keys
+- removed `legacy(account_id) -> account(id)`
+- added `payment(account_id) -> account(id)`
+## Enums
+- removed `color`
+- changed `status`: added values deleted, removed values blocked
+## Views
+- added `payment_account`
+"#
+    );
+    assert_eq!(SqlERDiff::default().summary(), "No changes\n");
+}
+
+#[tokio::test]
+async fn foreign_key_changes() {
+    let old = load_erd(
+        r#"
+        CREATE TABLE account (id int PRIMARY KEY);
+        CREATE TABLE payment (
+            id int PRIMARY KEY,
+            account_id int,
+            CONSTRAINT payment_account_fk FOREIGN KEY (account_id) REFERENCES account (id) ON DELETE CASCADE
+        );
+        "#,
+    )
+    .await;
+    let new = load_erd(
+        r#"
+        CREATE TABLE account (id int PRIMARY KEY);
+        CREATE TABLE payment (
+            id int PRIMARY KEY,
+            account_id int REFERENCES account ON DELETE RESTRICT DEFERRABLE
+        );
+        "#,
+    )
+    .await;
+    let diff = SqlERDiff::new(&old, &new);
+    assert_eq!(
+        diff.foreign_key_change(&new.foreign_keys[0]),
+        Some(Change::Changed)
+    );
+    assert_eq!(
+        diff.summary(),
+        r#"## Foreign keys
+- changed `payment(account_id) -> account(id)`: name `payment_account_fk` -> `payment_account_id_fkey`, ON DELETE `CASCADE` -> `RESTRICT`, deferrable `NOT DEFERRABLE` -> `DEFERRABLE INITIALLY IMMEDIATE`
+"#
+    );
+    let merged = diff.merge(&old, &new);
+    assert_eq!(merged.foreign_keys.len(), 1);
+}
+
+#[tokio::test]
+async fn plantuml() {
+    let result = generate(GeneratorType::PlantUML).await;
+    assert!(result.contains("table(account) #FFE0A3 {\n"));
+    assert!(result.contains("table(payment) #C8F7C5 {\n"));
+    assert!(result.contains("table(legacy) #F7C5C5 {\n"));
+    assert!(result.contains("  column(fax, \"text\", $diff=\"removed\")\n"));
+    assert!(
+        result.contains("  column(email, \"character varying\", $nn=true, $diff=\"changed\")\n")
+    );
+    assert!(result.contains("  column(status, \"status\")\n"));
+    assert!(result.contains("view(active_account) {\n"));
+    assert!(result.contains("view(payment_account) #C8F7C5 {\n"));
+    assert!(result.contains("payment }o-[#2E8B57]-|| account : payment_account_id_fkey\n"));
+    assert!(result.contains("legacy }o-[#D9534F]-|| account : legacy_account_id_fkey\n"));
+    assert!(result.contains("enum(color, \"red\", $color=\"#F7C5C5\")\n"));
+}
+
+#[tokio::test]
+async fn mermaid() {
+    let result = generate(GeneratorType::Mermaid).await;
+    assert!(result.contains("account:::changed {\n"));
+    assert!(result.contains("    text fax \"removed\"\n"));
+    assert!(result.contains("    timestamp_with_time_zone created_at \"added, default: now()\"\n"));
+    assert!(result.contains("    status status\n"));
+    assert!(result.contains("legacy }o--|| account: \"legacy_account_id_fkey, removed\"\n"));
+    assert!(result.contains("\"color (ENUM)\":::removed {\n"));
+    assert!(result.contains("classDef added fill:#C8F7C5\n"));
+}
+
+#[tokio::test]
+async fn source_loaders() {
+    let mut loader = lookup_source_loader(
+        "tests/test_db.json",
+        vec!["public".into()],
+        TableFilter::default(),
+        TlsConfig::default(),
+    )
+    .await
+    .unwrap();
+    let json_erd = loader.load_erd_data().await.unwrap();
+    let mut loader = lookup_source_loader(
+        "tests/test_db.sql",
+        vec!["public".into()],
+        TableFilter::default(),
+        TlsConfig::default(),
+    )
+    .await
+    .unwrap();
+    let ddl_erd = loader.load_erd_data().await.unwrap();
+    assert_eq!(json_erd.tables.len(), ddl_erd.tables.len());
+    assert_eq!(json_erd.foreign_keys.len(), ddl_erd.foreign_keys.len());
+}
+
+#[tokio::test]
+async fn casts_of_constraints() {
+    let old = load_erd(
+        "CREATE TABLE account (status text DEFAULT 'new', amount numeric CHECK (amount > 0));",
+    )
+    .await;
+    let new = load_erd(
+        "CREATE TABLE account (
+            status text DEFAULT 'new'::text,
+            amount numeric CHECK (amount > 0::numeric)
+        );",
+    )
+    .await;
+    assert!(SqlERDiff::new(&old, &new).is_empty());
+
+    let changed =
+        load_erd("CREATE TABLE account (status text DEFAULT 'old'::text, amount numeric);").await;
+    let diff = SqlERDiff::new(&old, &changed);
+    let status = &diff.tables["account"].columns["status"];
+    assert_eq!(
+        status.added_constraints,
+        vec![Default("'old'::text".into())]
+    );
+    assert_eq!(status.removed_constraints, vec![Default("'new'".into())]);
+    let amount = &diff.tables["account"].columns["amount"];
+    assert!(amount.added_constraints.is_empty());
+    assert_eq!(amount.removed_constraints, vec![Check("amount > 0".into())]);
+}
+
+#[tokio::test]
+async fn same_schema_of_ddl_and_database() {
+    // Hand-written DDL and pg_dump of the same database
+    for ddl in ["tests/test_db.sql", "tests/test_db_pg_dump.sql"] {
+        let mut erds = vec![];
+        for source in [ddl.to_string(), env::var("CON_STRING").unwrap()] {
+            let mut loader = lookup_source_loader(
+                &source,
+                vec!["public".into()],
+                TableFilter::default(),
+                TlsConfig::default(),
+            )
+            .await
+            .unwrap();
+            erds.push(loader.load_erd_data().await.unwrap());
+        }
+        let diff = SqlERDiff::new(&erds[0], &erds[1]);
+        assert!(diff.is_empty(), "{ddl}: {}", diff.summary());
+        assert!(SqlERDiff::new(&erds[1], &erds[0]).is_empty());
+    }
+}
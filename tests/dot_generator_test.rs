@@ -0,0 +1,115 @@
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader, get_generator, sql_entities::*, Direction,
+    GeneratorConfigOptions, GeneratorType,
+};
+
+async fn load_erd(schema_names: Vec<String>) -> SqlERData {
+    let mut parser = DdlERDLoader::from_files(
+        &["tests/test_db.sql", "tests/test_multi_schema.sql"],
+        schema_names,
+    )
+    .unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+fn generate(sql_er_data: SqlERData, opts: GeneratorConfigOptions) -> String {
+    get_generator(GeneratorType::Dot)
+        .unwrap()
+        .generate(sql_er_data, &opts)
+        .unwrap()
+}
+
+fn default_opts() -> GeneratorConfigOptions {
+    GeneratorConfigOptions::default()
+}
+
+#[tokio::test]
+async fn columns_order() {
+    let result = generate(load_erd(vec!["public".into()]).await, default_opts());
+    assert!(result.starts_with("digraph sqlant {\n"));
+    assert!(!result.contains("rankdir"));
+    // PKs, FKs, NOT NULL, others
+    let customer_order = "<TR><TD COLSPAN=\"3\" BGCOLOR=\"lightgrey\"><B>customer_order</B></TD></TR>\n\
+        <TR><TD ALIGN=\"LEFT\">PK</TD><TD ALIGN=\"LEFT\">id</TD><TD ALIGN=\"LEFT\">bigint</TD></TR>\n\
+        <TR><TD ALIGN=\"LEFT\">FK, NN</TD><TD ALIGN=\"LEFT\">customer_id</TD><TD ALIGN=\"LEFT\">bigint</TD></TR>\n\
+        <TR><TD ALIGN=\"LEFT\">NN</TD><TD ALIGN=\"LEFT\">delivery_method</TD><TD ALIGN=\"LEFT\">text</TD></TR>\n";
+    assert!(result.contains(customer_order));
+    assert!(
+        result.contains("<TR><TD ALIGN=\"LEFT\">UQ, NN</TD><TD ALIGN=\"LEFT\">phone_number</TD>")
+    );
+    assert!(result.contains("<B>top_customers <I>(view)</I></B>"));
+    assert!(!result.contains("cluster"));
+}
+
+#[tokio::test]
+async fn relations() {
+    let result = generate(load_erd(vec!["public".into()]).await, default_opts());
+    assert!(result.contains(
+        "\"order_detail\" -> \"customer_order\" [arrowtail=crowodot, arrowhead=teetee]\n"
+    ));
+    assert!(
+        result.contains("\"vendor_address\" -> \"vendor\" [arrowtail=teeodot, arrowhead=teetee]\n")
+    );
+}
+
+#[tokio::test]
+async fn direction() {
+    for (direction, rankdir) in [
+        (Direction::TB, "TB"),
+        (Direction::BT, "BT"),
+        (Direction::LR, "LR"),
+        (Direction::RL, "RL"),
+    ] {
+        let result = generate(
+            load_erd(vec!["public".into()]).await,
+            GeneratorConfigOptions {
+                direction: Some(direction),
+                ..default_opts()
+            },
+        );
+        assert!(result.contains(&format!("    rankdir={rankdir}\n")));
+    }
+}
+
+#[tokio::test]
+async fn clusters() {
+    let result = generate(
+        load_erd(vec!["public".into(), "billing".into()]).await,
+        default_opts(),
+    );
+    assert!(result.contains(
+        "    subgraph \"cluster_billing\" {\n    label=\"billing\"\n    \"billing.invoice\" [label=<"
+    ));
+    assert!(result.contains("    subgraph \"cluster_public\" {\n"));
+    assert!(result.contains("\"billing.invoice\" -> \"public.customer_order\""));
+}
+
+#[tokio::test]
+async fn conceptual_with_enums() {
+    let result = generate(
+        load_erd(vec!["public".into()]).await,
+        GeneratorConfigOptions {
+            conceptual_diagram: true,
+            draw_enums: true,
+            ..default_opts()
+        },
+    );
+    assert!(!result.contains("<TD ALIGN=\"LEFT\">PK</TD>"));
+    assert!(result.contains("\"product_category (ENUM)\" [label=<"));
+    assert!(result.contains("<TR><TD ALIGN=\"LEFT\">electronics</TD></TR>\n"));
+}
+
+#[tokio::test]
+async fn cascades() {
+    let result = generate(
+        load_erd(vec!["public".into()]).await,
+        GeneratorConfigOptions {
+            highlight_cascades: true,
+            ..default_opts()
+        },
+    );
+    assert!(result.contains(
+        "\"order_detail\" -> \"customer_order\" [arrowtail=crowodot, arrowhead=teetee, color=\"#C0392B\"]\n"
+    ));
+    assert!(result.contains("\"order_detail\" -> \"sku\" [arrowtail=crowodot, arrowhead=teetee]\n"));
+}
@@ -0,0 +1,241 @@
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader,
+    focus::{Focus, FocusDirection},
+    get_generator,
+    sql_entities::*,
+    GeneratorConfigOptions, GeneratorType, SqlantError,
+};
+
+async fn load_ddl_erd(files: &[&str], schema_names: &[&str]) -> SqlERData {
+    let schema_names = schema_names.iter().map(ToString::to_string).collect();
+    let mut parser = DdlERDLoader::from_files(files, schema_names).unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+async fn load_erd() -> SqlERData {
+    load_ddl_erd(&["tests/test_db.sql"], &["public"]).await
+}
+
+fn focus(roots: &[&str], depth: usize, direction: FocusDirection) -> Focus {
+    Focus {
+        roots: roots.iter().map(ToString::to_string).collect(),
+        depth,
+        direction,
+    }
+}
+
+fn neighbourhood(sql_er_data: &SqlERData, focus: &Focus) -> Vec<(String, usize)> {
+    focus
+        .neighbourhood(sql_er_data)
+        .unwrap()
+        .into_iter()
+        .collect()
+}
+
+fn generate(sql_er_data: SqlERData, focus: &Focus, generator_type: GeneratorType) -> String {
+    let focus_tables = focus.root_names(&sql_er_data).unwrap();
+    get_generator(generator_type)
+        .unwrap()
+        .generate(
+            focus.apply(sql_er_data).unwrap(),
+            &GeneratorConfigOptions {
+                conceptual_diagram: true,
+                focus_tables,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+}
+
+#[tokio::test]
+async fn directions() {
+    let sql_er_data = load_erd().await;
+    assert_eq!(
+        neighbourhood(
+            &sql_er_data,
+            &focus(&["order_detail"], 2, FocusDirection::Outgoing)
+        ),
+        vec![
+            ("customer".to_string(), 2),
+            ("customer_order".to_string(), 1),
+            ("order_detail".to_string(), 0),
+            ("product".to_string(), 2),
+            ("sku".to_string(), 1),
+        ]
+    );
+    assert_eq!(
+        neighbourhood(
+            &sql_er_data,
+            &focus(&["customer_order"], 2, FocusDirection::Incoming)
+        ),
+        vec![
+            ("customer_order".to_string(), 0),
+            ("order_detail".to_string(), 1),
+            ("order_detail_approval".to_string(), 2),
+        ]
+    );
+    assert_eq!(
+        neighbourhood(
+            &sql_er_data,
+            &focus(&["customer_order"], 1, FocusDirection::Both)
+        ),
+        vec![
+            ("customer".to_string(), 1),
+            ("customer_order".to_string(), 0),
+            ("order_detail".to_string(), 1),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn depth() {
+    let sql_er_data = load_erd().await;
+    assert_eq!(
+        neighbourhood(&sql_er_data, &focus(&["sku"], 0, FocusDirection::Both)),
+        vec![("sku".to_string(), 0)]
+    );
+    // Shortest distance is taken from the nearest root
+    assert_eq!(
+        neighbourhood(
+            &sql_er_data,
+            &focus(&["vendor", "customer"], 10, FocusDirection::Incoming)
+        ),
+        vec![
+            ("customer".to_string(), 0),
+            ("customer_order".to_string(), 1),
+            ("order_detail".to_string(), 2),
+            ("order_detail_approval".to_string(), 3),
+            ("product".to_string(), 1),
+            ("sku".to_string(), 2),
+            ("vendor".to_string(), 0),
+            ("vendor_address".to_string(), 1),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn apply() {
+    let sql_er_data = load_erd().await;
+    let sql_er_data = focus(&["sku"], 1, FocusDirection::Both)
+        .apply(sql_er_data)
+        .unwrap();
+    let tables: Vec<&str> = sql_er_data.tables.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(tables, vec!["order_detail", "product", "sku"]);
+    let fks: Vec<(&str, &str)> = sql_er_data
+        .foreign_keys
+        .iter()
+        .map(|fk| (fk.source_table.name.as_str(), fk.target_table.name.as_str()))
+        .collect();
+    assert_eq!(fks, vec![("order_detail", "sku"), ("sku", "product")]);
+    assert!(sql_er_data.views.is_empty());
+}
+
+#[tokio::test]
+async fn views() {
+    let mut parser = DdlERDLoader::new(
+        r#"
+        CREATE TABLE customer (id int PRIMARY KEY, name text);
+        CREATE TABLE customer_order (id int PRIMARY KEY, customer_id int REFERENCES customer);
+        CREATE TABLE product (id int PRIMARY KEY);
+        CREATE VIEW customer_name AS SELECT id, name FROM customer;
+        CREATE VIEW named_order AS
+            SELECT o.id, c.name FROM customer_order o JOIN customer_name c ON c.id = o.customer_id;
+        CREATE VIEW product_order AS
+            SELECT o.id FROM customer_order o, product p;
+        "#
+        .to_string(),
+        vec!["public".to_string()],
+    );
+    let sql_er_data = parser.load_erd_data().await.unwrap();
+    let sql_er_data = focus(&["customer"], 1, FocusDirection::Both)
+        .apply(sql_er_data)
+        .unwrap();
+    // View of view is kept, view of table outside of neighbourhood isn't
+    let views: Vec<(&str, Vec<&str>)> = sql_er_data
+        .views
+        .iter()
+        .map(|v| {
+            (
+                v.name.as_str(),
+                v.depends_on.iter().map(String::as_str).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        views,
+        vec![
+            ("customer_name", vec!["customer"]),
+            ("named_order", vec!["customer_name", "customer_order"]),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn qualified_roots() {
+    let sql_er_data = load_ddl_erd(
+        &["tests/test_db.sql", "tests/test_multi_schema.sql"],
+        &["public", "billing"],
+    )
+    .await;
+    let focus = focus(&["invoice"], 1, FocusDirection::Both);
+    assert_eq!(
+        focus.root_names(&sql_er_data).unwrap(),
+        vec!["billing.invoice"]
+    );
+    assert_eq!(
+        neighbourhood(&sql_er_data, &focus),
+        vec![
+            ("billing.invoice".to_string(), 0),
+            ("public.customer_order".to_string(), 1),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn errors() {
+    let sql_er_data = load_erd().await;
+    let err = focus(&["nope"], 1, FocusDirection::Both)
+        .apply(sql_er_data)
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "Table nope doesn't exist");
+    assert!(matches!(err, SqlantError::TableNotFound { name } if name == "nope"));
+
+    let mut parser = DdlERDLoader::new(
+        "CREATE TABLE a.item (id int); CREATE TABLE b.item (id int);".to_string(),
+        vec!["a".to_string(), "b".to_string()],
+    );
+    let sql_er_data = parser.load_erd_data().await.unwrap();
+    assert_eq!(
+        focus(&["item"], 1, FocusDirection::Both)
+            .root_names(&sql_er_data)
+            .err()
+            .unwrap()
+            .to_string(),
+        "Table item is ambiguous: a.item, b.item"
+    );
+    assert_eq!(
+        focus(&["b.item"], 1, FocusDirection::Both)
+            .root_names(&sql_er_data)
+            .unwrap(),
+        vec!["b.item"]
+    );
+}
+
+#[tokio::test]
+async fn emphasis() {
+    let focus = focus(&["sku"], 1, FocusDirection::Outgoing);
+    let result = generate(load_erd().await, &focus, GeneratorType::PlantUML);
+    assert!(result.contains("table(sku) #line.bold;line:DarkOrange {\n"));
+    assert!(result.contains("table(product) {\n"));
+    assert!(!result.contains("table(vendor)"));
+
+    let result = generate(load_erd().await, &focus, GeneratorType::Mermaid);
+    assert!(result.contains("classDef focus stroke:#FF8C00,stroke-width:3px\nclass sku focus\n"));
+
+    let result = generate(load_erd().await, &focus, GeneratorType::Dot);
+    assert!(result.contains(
+        "    \"sku\" [label=<\n<TABLE BORDER=\"2\" COLOR=\"darkorange\" CELLBORDER=\"1\""
+    ));
+    assert!(result.contains("    \"product\" [label=<\n<TABLE BORDER=\"0\" CELLBORDER=\"1\""));
+}
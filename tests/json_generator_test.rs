@@ -0,0 +1,88 @@
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader, get_generator, json_model::*, sql_entities::*,
+    GeneratorConfigOptions, GeneratorType,
+};
+
+async fn load_erd() -> SqlERData {
+    let mut parser = DdlERDLoader::from_files(
+        &["tests/test_db.sql", "tests/test_multi_schema.sql"],
+        vec!["public".to_string(), "billing".to_string()],
+    )
+    .unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+fn generate(sql_er_data: SqlERData) -> String {
+    get_generator(GeneratorType::Json)
+        .unwrap()
+        .generate(sql_er_data, &GeneratorConfigOptions::default())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let sql_er_data = load_erd().await;
+    let expected = JsonERData::from(&sql_er_data);
+    let result: JsonERData = serde_json::from_str(&generate(sql_er_data)).unwrap();
+    assert_eq!(result, expected);
+    assert_eq!(result.version, JSON_FORMAT_VERSION);
+    assert_eq!(result.tables.len(), 9);
+    assert_eq!(result.views.len(), 4);
+}
+
+#[tokio::test]
+async fn format() {
+    let json: serde_json::Value = serde_json::from_str(&generate(load_erd().await)).unwrap();
+    assert_eq!(json["version"], 1);
+    let fk = json["foreign_keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|fk| fk["source_table"] == "billing.invoice")
+        .unwrap();
+    assert_eq!(
+        *fk,
+        serde_json::json!({
+            "source_table": "billing.invoice",
+            "source_columns": ["customer_order_id"],
+            "target_table": "public.customer_order",
+            "target_columns": ["id"],
+            "name": "invoice_customer_order_id_fkey",
+            "on_delete": "no_action",
+            "on_update": "no_action",
+            "deferrable": false,
+            "initially_deferred": false,
+            "match_type": "simple",
+        })
+    );
+    let customer_order = json["tables"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "public.customer_order")
+        .unwrap();
+    assert_eq!(customer_order["schema"], "public");
+    let total_tax_amount = customer_order["columns"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == "total_tax_amount")
+        .unwrap();
+    assert_eq!(
+        total_tax_amount["constraints"],
+        serde_json::json!([
+            "not_null",
+            {"check": "total_tax_amount <= total_price"},
+            {"default": "0"}
+        ])
+    );
+    // Enums of several schemas are qualified like tables
+    assert_eq!(
+        json["enums"]["public.product_category"],
+        serde_json::json!(["electronics", "jewelry", "home"])
+    );
+    assert_eq!(
+        json["enums"]["billing.product_category"],
+        serde_json::json!(["subscription", "service"])
+    );
+}
@@ -0,0 +1,172 @@
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader, get_generator, json_erd_loader::JsonERDLoader, sql_entities::*,
+    GeneratorConfigOptions, GeneratorType, SqlantError,
+};
+
+mod utils;
+use crate::utils::check_fk;
+
+// tests/test_db.json is exported from tests/test_db.sql database with `-o json`
+async fn load_erd() -> SqlERData {
+    let mut parser = JsonERDLoader::from_file("tests/test_db.json").unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+async fn load_erd_from_json(json: &str) -> Result<SqlERData, SqlantError> {
+    let mut parser = JsonERDLoader::new(json.to_string());
+    parser.load_erd_data().await
+}
+
+fn generate(sql_er_data: SqlERData, generator_type: GeneratorType) -> String {
+    get_generator(generator_type)
+        .unwrap()
+        .generate(
+            sql_er_data,
+            &GeneratorConfigOptions {
+                draw_enums: true,
+                // Database normalizes predicates of partial indexes
+                draw_indexes: false,
+                draw_comments: true,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+}
+
+#[tokio::test]
+async fn tables_data() {
+    let sql_er_data = load_erd().await;
+    assert_eq!(sql_er_data.tables.len(), 8);
+    assert_eq!(sql_er_data.foreign_keys.len(), 7);
+    assert_eq!(sql_er_data.views.len(), 2);
+    for fk in &sql_er_data.foreign_keys {
+        assert_eq!(
+            fk.is_zero_one_to_one,
+            fk.source_table.name == "vendor_address"
+                || fk.source_table.name == "order_detail_approval"
+        );
+    }
+    for tbl in &sql_er_data.tables {
+        assert_eq!(
+            tbl.has_composite_pk,
+            tbl.name == "order_detail_approval" || tbl.name == "order_detail"
+        );
+    }
+    check_fk(
+        &sql_er_data,
+        "order_detail_approval",
+        "order_detail",
+        vec!["order_detail_id", "customer_order_id"],
+        vec!["id", "customer_order_id"],
+    );
+}
+
+#[tokio::test]
+async fn ddl_loader_matches_snapshot() {
+    let mut parser =
+        DdlERDLoader::from_files(&["tests/test_db.sql"], vec!["public".into()]).unwrap();
+    let ddl_er_data = parser.load_erd_data().await.unwrap();
+    for generator_type in [GeneratorType::PlantUML, GeneratorType::Mermaid] {
+        assert_eq!(
+            generate(ddl_er_data.clone(), generator_type.clone()),
+            generate(load_erd().await, generator_type)
+        );
+    }
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let json = generate(load_erd().await, GeneratorType::Json);
+    let sql_er_data = load_erd_from_json(&json).await.unwrap();
+    assert_eq!(generate(sql_er_data, GeneratorType::Json), json);
+}
+
+#[tokio::test]
+async fn fk_actions() {
+    let fk_json = |fields: &str| {
+        format!(
+            r#"{{
+            "version": 1,
+            "tables": [
+                {{"name": "a", "schema": null, "indexes": [], "comment": null, "columns": [
+                    {{"name": "id", "col_num": 1, "datatype": "int", "constraints": ["primary_key"], "comment": null}}
+                ]}}
+            ],
+            "foreign_keys": [
+                {{"source_table": "a", "source_columns": ["id"], "target_table": "a", "target_columns": ["id"]{fields}}}
+            ],
+            "enums": {{}},
+            "views": []
+        }}"#
+        )
+    };
+    // Snapshots without actions have default ones
+    let sql_er_data = load_erd_from_json(&fk_json("")).await.unwrap();
+    assert!(sql_er_data.foreign_keys[0].clauses().is_empty());
+
+    let sql_er_data = load_erd_from_json(&fk_json(
+        r#", "on_delete": "set_null", "deferrable": true, "match_type": "full""#,
+    ))
+    .await
+    .unwrap();
+    let fk = &sql_er_data.foreign_keys[0];
+    assert_eq!(fk.on_delete, ReferentialAction::SetNull);
+    assert_eq!(fk.match_type, MatchType::Full);
+    assert_eq!(
+        fk.clauses(),
+        vec!["ON DELETE SET NULL", "DEFERRABLE", "MATCH FULL"]
+    );
+}
+
+#[tokio::test]
+async fn errors() {
+    let err = load_erd_from_json(
+        r#"{"version": 0, "tables": [], "foreign_keys": [], "enums": {}, "views": []}"#,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unsupported version 0 of JSON format (expected 1)"
+    );
+    assert!(matches!(
+        err,
+        SqlantError::UnsupportedVersion {
+            found: 0,
+            supported: 1
+        }
+    ));
+
+    let err = load_erd_from_json(
+        r#"{
+            "version": 1,
+            "tables": [{"name": "a", "schema": null, "columns": [], "indexes": [], "comment": null}],
+            "foreign_keys": [
+                {"source_table": "a", "source_columns": ["b_id"], "target_table": "b", "target_columns": ["id"]}
+            ],
+            "enums": {},
+            "views": []
+        }"#,
+    )
+    .await
+    .unwrap_err();
+    match err {
+        SqlantError::DanglingForeignKey {
+            source_table,
+            source_columns,
+            target_table,
+            target_columns,
+        } => {
+            assert_eq!(source_table, "a");
+            assert_eq!(source_columns, vec!["b_id"]);
+            assert_eq!(target_table, "b");
+            assert_eq!(target_columns, vec!["id"]);
+        }
+        e => panic!("DanglingForeignKey error is expected, got {e}"),
+    }
+
+    assert!(matches!(
+        load_erd_from_json("{").await.unwrap_err(),
+        SqlantError::Json(_)
+    ));
+}
@@ -0,0 +1,241 @@
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader, get_generator, sql_entities::*, GeneratorConfigOptions,
+    GeneratorType,
+};
+use std::sync::Arc;
+
+async fn load_erd() -> SqlERData {
+    let mut parser =
+        DdlERDLoader::from_files(&["tests/test_db.sql"], vec!["public".to_string()]).unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+fn opts(conceptual_diagram: bool) -> GeneratorConfigOptions {
+    GeneratorConfigOptions {
+        conceptual_diagram,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn views() {
+    let generator = get_generator(GeneratorType::Mermaid).unwrap();
+    let result = generator.generate(load_erd().await, &opts(false)).unwrap();
+    assert!(result.contains(
+        "\"top_customers (V)\" {\n    bigint customer_id\n    bigint total_orders\n    numeric total_spent\n}\n"
+    ));
+    assert!(result.contains("\"monthly_sales_summary (MV)\" {\n    date month\n"));
+    assert!(result.contains("\"top_customers (V)\" }o..o{ customer_order: \"depends on\"\n"));
+    assert!(
+        result.contains("\"monthly_sales_summary (MV)\" }o..o{ customer_order: \"depends on\"\n")
+    );
+
+    let result = generator.generate(load_erd().await, &opts(true)).unwrap();
+    assert!(result.contains("\"top_customers (V)\" {\n}\n"));
+    assert!(result.contains("\"monthly_sales_summary (MV)\" {\n}\n"));
+}
+
+#[tokio::test]
+async fn dependencies() {
+    let generator = get_generator(GeneratorType::Mermaid).unwrap();
+    let mut sql_erd = load_erd().await;
+    for view in sql_erd.views.iter_mut() {
+        Arc::make_mut(view).depends_on = vec!["customer_order".to_string()];
+    }
+    let result = generator.generate(sql_erd.clone(), &opts(false)).unwrap();
+    assert!(result.contains("\"top_customers (V)\" }o..o{ customer_order: \"depends on\"\n"));
+    assert!(
+        result.contains("\"monthly_sales_summary (MV)\" }o..o{ customer_order: \"depends on\"\n")
+    );
+
+    // Dependencies on entities which aren't drawn are skipped
+    sql_erd.tables.retain(|tbl| tbl.name != "customer_order");
+    sql_erd.foreign_keys.clear();
+    let result = generator.generate(sql_erd, &opts(false)).unwrap();
+    assert!(result.contains("\"top_customers (V)\" {"));
+    assert!(!result.contains("depends on"));
+}
+
+#[tokio::test]
+async fn conceptual() {
+    let mut parser = DdlERDLoader::new(
+        r#"
+        CREATE TABLE student (id int PRIMARY KEY, name text);
+        CREATE TABLE course (id int PRIMARY KEY, title text);
+        CREATE TABLE enrollment (
+            student_id int REFERENCES student,
+            course_id int REFERENCES course,
+            grade int,
+            PRIMARY KEY (student_id, course_id)
+        );
+        CREATE TABLE teacher (id int PRIMARY KEY, course_id int CONSTRAINT teaches REFERENCES course);
+        -- Junction table that is referenced by another table isn't collapsed
+        CREATE TABLE course_author (
+            course_id int REFERENCES course,
+            teacher_id int REFERENCES teacher,
+            PRIMARY KEY (course_id, teacher_id)
+        );
+        CREATE TABLE royalty (
+            course_id int,
+            teacher_id int,
+            FOREIGN KEY (course_id, teacher_id) REFERENCES course_author (course_id, teacher_id)
+        );
+        "#
+        .to_string(),
+        vec!["public".to_string()],
+    );
+    let sql_erd = parser.load_erd_data().await.unwrap();
+    let generator = get_generator(GeneratorType::Mermaid).unwrap();
+
+    let result = generator.generate(sql_erd.clone(), &opts(true)).unwrap();
+    assert!(result.contains("student {\n}\n"));
+    assert!(!result.contains("integer id"));
+    assert!(!result.contains("enrollment {"));
+    assert!(result.contains("student }o--o{ course: \"enrollment\"\n"));
+    assert!(result.contains("teacher }o--|| course: \"teaches\"\n"));
+    assert!(result.contains("course_author {\n}\n"));
+    assert!(result.contains("course_author }o--|| teacher: \"course_author_teacher_id_fkey\"\n"));
+
+    let result = generator.generate(sql_erd, &opts(false)).unwrap();
+    assert!(result.contains("enrollment {\n    integer course_id PK,FK\n"));
+    assert!(result.contains("enrollment }o--|| student: \"enrollment_student_id_fkey\"\n"));
+    assert!(!result.contains("}o--o{"));
+}
+
+#[tokio::test]
+async fn legend() {
+    let generator = get_generator(GeneratorType::Mermaid).unwrap();
+    let result = generator.generate(load_erd().await, &opts(false)).unwrap();
+    assert!(!result.contains("\"sqlant legend\" {"));
+
+    let result = generator
+        .generate(
+            load_erd().await,
+            &GeneratorConfigOptions {
+                draw_legend: true,
+                not_null: true,
+                ..opts(false)
+            },
+        )
+        .unwrap();
+    assert!(result.ends_with(
+        "\"sqlant legend\" {\n    _ _ PK \"Primary Key\"\n    _ _ FK \"Foreign Key\"\n    _ _ UK \"Unique\"\n    _ _ \"NN: Mandatory field (Not Null)\"\n    _ _ \"(V): View\"\n    _ _ \"(MV): Materialized View\"\n    _ _ \"}o..o{: View reads from table or view\"\n}\n"
+    ));
+
+    // Conceptual diagram has no attributes, so keys aren't explained
+    let result = generator
+        .generate(
+            load_erd().await,
+            &GeneratorConfigOptions {
+                draw_legend: true,
+                draw_enums: true,
+                ..opts(true)
+            },
+        )
+        .unwrap();
+    assert!(!result.contains("\"Primary Key\""));
+    assert!(result.contains("    _ _ \"(ENUM): Enum\"\n"));
+
+    let mut sql_erd = load_erd().await;
+    Arc::make_mut(&mut sql_erd.views[0]).depends_on = vec!["customer_order".to_string()];
+    let result = generator
+        .generate(
+            sql_erd,
+            &GeneratorConfigOptions {
+                draw_legend: true,
+                ..opts(true)
+            },
+        )
+        .unwrap();
+    assert!(result.contains("    _ _ \"}o..o{: View reads from table or view\"\n"));
+
+    // Table named legend isn't merged with legend
+    let mut parser = DdlERDLoader::new(
+        "CREATE TABLE legend (id int PRIMARY KEY);".to_string(),
+        vec!["public".to_string()],
+    );
+    let result = generator
+        .generate(
+            parser.load_erd_data().await.unwrap(),
+            &GeneratorConfigOptions {
+                draw_legend: true,
+                ..opts(false)
+            },
+        )
+        .unwrap();
+    assert!(result.contains("legend {\n    integer id PK\n}\n"));
+    assert!(result.contains("\"sqlant legend\" {\n"));
+}
+
+#[tokio::test]
+async fn lineage() {
+    let generator = get_generator(GeneratorType::Mermaid).unwrap();
+    let mut sql_erd = load_erd().await;
+    let top_customers = sql_erd
+        .views
+        .iter_mut()
+        .find(|v| v.name == "top_customers")
+        .unwrap();
+    Arc::make_mut(top_customers).used_columns = [(
+        "customer_order".to_string(),
+        vec!["customer_id".to_string(), "total_price".to_string()],
+    )]
+    .into();
+
+    let result = generator.generate(sql_erd.clone(), &opts(false)).unwrap();
+    assert!(result.contains("\"top_customers (V)\" }o..o{ customer_order: \"depends on\"\n"));
+
+    let result = generator
+        .generate(
+            sql_erd,
+            &GeneratorConfigOptions {
+                draw_lineage: true,
+                ..opts(false)
+            },
+        )
+        .unwrap();
+    assert!(result
+        .contains("\"top_customers (V)\" }o..o{ customer_order: \"customer_id, total_price\"\n"));
+    // Columns of materialized view aren't resolved
+    assert!(
+        result.contains("\"monthly_sales_summary (MV)\" }o..o{ customer_order: \"depends on\"\n")
+    );
+}
+
+#[tokio::test]
+async fn fk_labels() {
+    let generator = get_generator(GeneratorType::Mermaid).unwrap();
+    let result = generator.generate(load_erd().await, &opts(false)).unwrap();
+    assert!(result.contains("order_detail }o--|| sku: \"order_detail_sku_id_fkey\"\n"));
+    assert!(result.contains(
+        "order_detail }o--|| customer_order: \"order_detail_customer_order_id_fkey, ON DELETE CASCADE\"\n"
+    ));
+    assert!(result.contains(
+        "order_detail_approval |o--|| order_detail: \"order_detail_approval_order_detail_id_customer_order_id_fkey, ON UPDATE CASCADE, DEFERRABLE INITIALLY DEFERRED\"\n"
+    ));
+}
+
+#[tokio::test]
+async fn schemas() {
+    let mut parser = DdlERDLoader::from_files(
+        &["tests/test_db.sql", "tests/test_multi_schema.sql"],
+        vec!["public".to_string(), "billing".to_string()],
+    )
+    .unwrap();
+    let sql_erd = parser.load_erd_data().await.unwrap();
+    let generator = get_generator(GeneratorType::Mermaid).unwrap();
+    let result = generator
+        .generate(
+            sql_erd,
+            &GeneratorConfigOptions {
+                draw_enums: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    // Dots aren't allowed in attribute types
+    assert!(result.contains("    billing_product_category category\n"));
+    assert!(result.contains("    public_product_category category\n"));
+    assert!(result.contains("\"billing.product_category (ENUM)\" {"));
+    assert!(result.contains("\"billing.invoice\" {"));
+}
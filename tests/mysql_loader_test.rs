@@ -0,0 +1,170 @@
+use sqlant::{
+    lookup_loader, sql_entities::ColumnConstraints::*, sql_entities::*, table_filter::TableFilter,
+    tls::TlsConfig,
+};
+use std::{collections::BTreeMap, env};
+
+mod utils;
+use crate::utils::check_fk;
+
+async fn load_erd() -> SqlERData {
+    let mut parser = lookup_loader(
+        &env::var("MYSQL_CON_STRING").unwrap(),
+        vec!["public".to_string()],
+        TableFilter::default(),
+        TlsConfig::default(),
+    )
+    .await
+    .unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires MySQL, set MYSQL_CON_STRING (see docker-compose.yml)"]
+async fn enums() {
+    let sql_er_data = load_erd().await;
+    let expected = BTreeMap::from([(
+        "product_category".to_string(),
+        vec![
+            "electronics".to_string(),
+            "jewelry".to_string(),
+            "home".to_string(),
+        ],
+    )]);
+    assert_eq!(sql_er_data.enums, expected);
+}
+
+#[tokio::test]
+#[ignore = "requires MySQL, set MYSQL_CON_STRING (see docker-compose.yml)"]
+async fn columns() {
+    let sql_er_data = load_erd().await;
+    let tables = BTreeMap::from([
+        (
+            "customer",
+            vec![
+                ("id", "bigint", vec![PrimaryKey, NotNull, Unique]),
+                ("name", "text", vec![NotNull]),
+                ("email", "varchar", vec![NotNull, Unique]),
+                ("registered_at", "timestamp", vec![NotNull]),
+            ],
+        ),
+        (
+            "product",
+            vec![
+                ("id", "bigint", vec![PrimaryKey, NotNull, Unique]),
+                ("vendor_id", "bigint", vec![ForeignKey, NotNull]),
+                ("name", "text", vec![NotNull]),
+                ("category", "product_category", vec![NotNull]),
+            ],
+        ),
+        (
+            "order_detail_approval",
+            vec![
+                (
+                    "order_detail_id",
+                    "bigint",
+                    vec![PrimaryKey, ForeignKey, NotNull, Unique],
+                ),
+                (
+                    "customer_order_id",
+                    "bigint",
+                    vec![PrimaryKey, ForeignKey, NotNull, Unique],
+                ),
+                ("approved_at", "timestamp", vec![NotNull]),
+            ],
+        ),
+    ]);
+
+    for (table_name, cols) in tables {
+        let table = sql_er_data
+            .tables
+            .iter()
+            .find(|&t| t.name == table_name)
+            .unwrap();
+        assert_eq!(cols.len(), table.columns.len());
+        for (exp_col_name, exp_col_type, exp_constraints) in cols {
+            let col = table
+                .columns
+                .iter()
+                .find(|col| col.name == exp_col_name)
+                .unwrap();
+            assert_eq!(col.constraints, exp_constraints.into_iter().collect());
+            assert_eq!(col.datatype, exp_col_type);
+        }
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires MySQL, set MYSQL_CON_STRING (see docker-compose.yml)"]
+async fn fks() {
+    let sql_er_data = load_erd().await;
+    check_fk(
+        &sql_er_data,
+        "order_detail_approval",
+        "order_detail",
+        vec!["order_detail_id", "customer_order_id"],
+        vec!["id", "customer_order_id"],
+    );
+    check_fk(
+        &sql_er_data,
+        "order_detail",
+        "customer_order",
+        vec!["customer_order_id"],
+        vec!["id"],
+    );
+    check_fk(
+        &sql_er_data,
+        "vendor_address",
+        "vendor",
+        vec!["vendor_id"],
+        vec!["id"],
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires MySQL, set MYSQL_CON_STRING (see docker-compose.yml)"]
+async fn fk_actions() {
+    let sql_er_data = load_erd().await;
+    let fk = |source_table: &str, target_table: &str| {
+        sql_er_data
+            .foreign_keys
+            .iter()
+            .find(|fk| fk.source_table.name == source_table && fk.target_table.name == target_table)
+            .unwrap()
+    };
+    assert_eq!(
+        fk("order_detail", "customer_order").on_delete,
+        ReferentialAction::Cascade
+    );
+    assert_eq!(
+        fk("order_detail_approval", "order_detail").on_update,
+        ReferentialAction::Cascade
+    );
+    // MySQL doesn't support deferrable constraints
+    assert!(!fk("order_detail_approval", "order_detail").deferrable);
+}
+
+#[tokio::test]
+#[ignore = "requires MySQL, set MYSQL_CON_STRING (see docker-compose.yml)"]
+async fn tables_data() {
+    let sql_er_data = load_erd().await;
+    assert_eq!(sql_er_data.tables.len(), 7);
+    assert_eq!(sql_er_data.foreign_keys.len(), 6);
+    assert_eq!(sql_er_data.views.len(), 1);
+    assert_eq!(sql_er_data.views[0].name, "top_customers");
+}
+
+#[tokio::test]
+#[ignore = "requires MySQL, set MYSQL_CON_STRING (see docker-compose.yml)"]
+async fn is_zero_one_to_one() {
+    let sql_er_data = load_erd().await;
+    for fk in &sql_er_data.foreign_keys {
+        if fk.source_table.name == "vendor_address"
+            || fk.source_table.name == "order_detail_approval"
+        {
+            assert!(fk.is_zero_one_to_one);
+        } else {
+            assert!(!fk.is_zero_one_to_one);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::{fs, path::Path};
+
+use sqlant::{output::write_file, GeneratorType, SqlantError};
+
+#[test]
+fn from_extension() {
+    for (path, generator_type) in [
+        ("erd.puml", Some(GeneratorType::PlantUML)),
+        ("docs/erd.mmd", Some(GeneratorType::Mermaid)),
+        ("erd.dot", Some(GeneratorType::Dot)),
+        ("schema.dbml", Some(GeneratorType::Dbml)),
+        ("schema.json", Some(GeneratorType::Json)),
+        ("erd.txt", None),
+        ("erd", None),
+    ] {
+        assert_eq!(
+            GeneratorType::from_extension(Path::new(path)),
+            generator_type
+        );
+    }
+}
+
+#[test]
+fn write() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("docs").join("db").join("erd.puml");
+    write_file(&path, "@startuml\n@enduml\n").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "@startuml\n@enduml\n");
+
+    // Existing file is replaced and temporary file is removed
+    write_file(&path, "erDiagram\n").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "erDiagram\n");
+    let files: Vec<_> = fs::read_dir(path.parent().unwrap())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(files, vec!["erd.puml"]);
+
+    match write_file(dir.path(), "erDiagram\n") {
+        Err(SqlantError::Io { path, .. }) => assert_eq!(path, dir.path()),
+        _ => panic!("Io error is expected"),
+    }
+
+    // Failed write is reported with path of temporary file
+    let tmp_path = dir
+        .path()
+        .join(format!(".erd.mmd.{}.tmp", std::process::id()));
+    fs::create_dir(&tmp_path).unwrap();
+    match write_file(&dir.path().join("erd.mmd"), "erDiagram\n") {
+        Err(SqlantError::Io { path, .. }) => assert_eq!(path, tmp_path),
+        _ => panic!("Io error is expected"),
+    }
+}
@@ -0,0 +1,201 @@
+use std::fs;
+
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader,
+    get_generator,
+    partition::{load_groups, split_by_components, split_by_groups, write_partitions, Partition},
+    sql_entities::*,
+    GeneratorConfigOptions, GeneratorType,
+};
+
+static SQL: &str = r#"
+    CREATE TYPE order_status AS ENUM ('new', 'paid');
+    CREATE TYPE user_role AS ENUM ('admin', 'customer');
+    CREATE TABLE customer (id int PRIMARY KEY);
+    CREATE TABLE customer_order (
+        id int PRIMARY KEY,
+        customer_id int REFERENCES customer,
+        status order_status
+    );
+    CREATE TABLE order_item (id int PRIMARY KEY, order_id int REFERENCES customer_order);
+    CREATE TABLE app_user (id int PRIMARY KEY, role user_role);
+    CREATE TABLE app_session (id int PRIMARY KEY, user_id int REFERENCES app_user);
+    CREATE TABLE setting (name text PRIMARY KEY);
+    CREATE VIEW big_order AS SELECT id FROM customer_order;
+"#;
+
+async fn load_erd() -> SqlERData {
+    let mut parser = DdlERDLoader::new(SQL.to_string(), vec!["public".to_string()]);
+    parser.load_erd_data().await.unwrap()
+}
+
+// Name of partition with its tables, views and enums
+type PartitionSummary<'a> = (&'a str, Vec<&'a str>, Vec<&'a str>, Vec<&'a str>);
+
+fn summary(partitions: &[Partition]) -> Vec<PartitionSummary<'_>> {
+    partitions
+        .iter()
+        .map(|p| {
+            (
+                p.name.as_str(),
+                p.sql_erd.tables.iter().map(|t| t.name.as_str()).collect(),
+                p.sql_erd.views.iter().map(|v| v.name.as_str()).collect(),
+                p.sql_erd.enums.keys().map(String::as_str).collect(),
+            )
+        })
+        .collect()
+}
+
+fn fks(partition: &Partition) -> Vec<(&str, &str)> {
+    partition
+        .sql_erd
+        .foreign_keys
+        .iter()
+        .map(|fk| (fk.source_table.name.as_str(), fk.target_table.name.as_str()))
+        .collect()
+}
+
+#[tokio::test]
+async fn components() {
+    let sql_er_data = load_erd().await;
+    let partitions = split_by_components(&sql_er_data);
+    assert_eq!(
+        summary(&partitions),
+        vec![
+            (
+                "app_session",
+                vec!["app_session", "app_user"],
+                vec![],
+                vec!["user_role"]
+            ),
+            (
+                "customer_order",
+                vec!["customer", "customer_order", "order_item"],
+                vec![],
+                vec!["order_status"]
+            ),
+            ("others", vec!["setting"], vec!["big_order"], vec![]),
+        ]
+    );
+    assert_eq!(
+        fks(&partitions[1]),
+        vec![
+            ("customer_order", "customer"),
+            ("order_item", "customer_order")
+        ]
+    );
+}
+
+#[tokio::test]
+async fn groups() {
+    let sql_er_data = load_erd().await;
+    let groups_file = tempfile::NamedTempFile::new().unwrap();
+    fs::write(
+        groups_file.path(),
+        r#"{"orders": ["customer*", "order_*"], "users": ["app_*", "customer"]}"#,
+    )
+    .unwrap();
+    let groups = load_groups(groups_file.path().to_str().unwrap()).unwrap();
+    let partitions = split_by_groups(&sql_er_data, &groups);
+    assert_eq!(
+        summary(&partitions),
+        vec![
+            (
+                "orders",
+                vec!["customer", "customer_order", "order_item"],
+                vec![],
+                vec!["order_status"]
+            ),
+            (
+                "users",
+                vec!["app_session", "app_user", "customer"],
+                vec![],
+                vec!["user_role"]
+            ),
+            ("others", vec!["setting"], vec!["big_order"], vec![]),
+        ]
+    );
+    assert_eq!(fks(&partitions[1]), vec![("app_session", "app_user")]);
+
+    fs::write(groups_file.path(), r#"{"orders": ["order_["]}"#).unwrap();
+    assert!(load_groups(groups_file.path().to_str().unwrap()).is_err());
+    assert!(load_groups("tests/not_found.json").is_err());
+}
+
+#[tokio::test]
+async fn write() {
+    let sql_er_data = load_erd().await;
+    let out_dir = tempfile::tempdir().unwrap();
+    let out_dir = out_dir.path().join("erd");
+    let generator = get_generator(GeneratorType::Mermaid).unwrap();
+    write_partitions(
+        split_by_components(&sql_er_data),
+        generator.as_ref(),
+        &GeneratorConfigOptions {
+            conceptual_diagram: true,
+            ..Default::default()
+        },
+        &out_dir,
+        GeneratorType::Mermaid.extension(),
+    )
+    .unwrap();
+    assert_eq!(
+        fs::read_to_string(out_dir.join("index.md")).unwrap(),
+        r#"# ER diagrams
+
+- [app_session](app_session.mmd): app_session, app_user
+- [customer_order](customer_order.mmd): customer, customer_order, order_item
+- [others](others.mmd): setting, big_order
+"#
+    );
+    let app_session = fs::read_to_string(out_dir.join("app_session.mmd")).unwrap();
+    assert!(app_session.contains("app_session }o--|| app_user"));
+    assert!(!app_session.contains("customer"));
+    assert!(out_dir.join("others.mmd").exists());
+}
+
+#[tokio::test]
+async fn write_multi_schema() {
+    let sql = r#"
+        CREATE TABLE billing.customer (id int PRIMARY KEY);
+        CREATE TABLE billing.invoice (id int PRIMARY KEY, customer_id int REFERENCES billing.customer);
+        CREATE TABLE billing.others (id int PRIMARY KEY);
+        CREATE TABLE billing.others_item (id int PRIMARY KEY, others_id int REFERENCES billing.others);
+        CREATE TABLE crm.customer (id int PRIMARY KEY);
+        CREATE TABLE crm.contact (id int PRIMARY KEY, customer_id int REFERENCES crm.customer);
+        CREATE TABLE crm.note (id int PRIMARY KEY, customer_id int REFERENCES crm.customer);
+        CREATE TABLE crm.setting (name text PRIMARY KEY);
+    "#;
+    let mut parser = DdlERDLoader::new(
+        sql.to_string(),
+        vec!["billing".to_string(), "crm".to_string()],
+    );
+    let sql_er_data = parser.load_erd_data().await.unwrap();
+    let out_dir = tempfile::tempdir().unwrap();
+    let generator = get_generator(GeneratorType::Mermaid).unwrap();
+    write_partitions(
+        split_by_components(&sql_er_data),
+        generator.as_ref(),
+        &GeneratorConfigOptions {
+            conceptual_diagram: true,
+            ..Default::default()
+        },
+        out_dir.path(),
+        GeneratorType::Mermaid.extension(),
+    )
+    .unwrap();
+    assert_eq!(
+        fs::read_to_string(out_dir.path().join("index.md")).unwrap(),
+        r#"# ER diagrams
+
+- [billing.customer](billing.customer.mmd): billing.customer, billing.invoice
+- [others](others.mmd): billing.others, billing.others_item
+- [crm.customer](crm.customer.mmd): crm.contact, crm.customer, crm.note
+- [others](others_2.mmd): crm.setting
+"#
+    );
+    let others = fs::read_to_string(out_dir.path().join("others.mmd")).unwrap();
+    assert!(others.contains("billing.others_item"));
+    let crm = fs::read_to_string(out_dir.path().join("crm.customer.mmd")).unwrap();
+    assert!(crm.contains("crm.note"));
+}
@@ -0,0 +1,141 @@
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader, get_generator, sql_entities::*, Direction,
+    GeneratorConfigOptions, GeneratorType, SqlantError,
+};
+use std::sync::Arc;
+
+async fn load_erd() -> SqlERData {
+    let mut parser =
+        DdlERDLoader::from_files(&["tests/test_db.sql"], vec!["public".to_string()]).unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+fn opts(direction: Direction) -> GeneratorConfigOptions {
+    GeneratorConfigOptions {
+        direction: Some(direction),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn direction() {
+    let generator = get_generator(GeneratorType::PlantUML).unwrap();
+    for (direction, expected) in [
+        (Direction::TB, "top to bottom direction"),
+        (Direction::LR, "left to right direction"),
+    ] {
+        let result = generator
+            .generate(load_erd().await, &opts(direction))
+            .unwrap();
+        assert!(result.contains(expected));
+    }
+
+    for direction in [Direction::BT, Direction::RL] {
+        match generator.generate(load_erd().await, &opts(direction.clone())) {
+            Err(SqlantError::UnsupportedDirection {
+                generator,
+                direction: unsupported,
+            }) => {
+                assert_eq!(generator, GeneratorType::PlantUML);
+                assert_eq!(unsupported, direction);
+            }
+            _ => panic!("UnsupportedDirection error is expected"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn view_dependencies() {
+    let generator = get_generator(GeneratorType::PlantUML).unwrap();
+    let mut sql_erd = load_erd().await;
+    let result = generator
+        .generate(sql_erd.clone(), &opts(Direction::TB))
+        .unwrap();
+    assert!(result.contains("\ntop_customers ..> customer_order\n"));
+    assert!(result.contains("\nmonthly_sales_summary ..> customer_order\n"));
+
+    // Columns are resolved only by PostgreSQL loader
+    for view in sql_erd.views.iter_mut() {
+        Arc::make_mut(view).used_columns = [(
+            "customer_order".to_string(),
+            vec!["customer_id".to_string(), "total_price".to_string()],
+        )]
+        .into();
+    }
+    let result = generator
+        .generate(
+            sql_erd,
+            &GeneratorConfigOptions {
+                draw_lineage: true,
+                ..opts(Direction::TB)
+            },
+        )
+        .unwrap();
+    assert!(result.contains("\ntop_customers ..> customer_order : customer_id, total_price\n"));
+}
+
+#[tokio::test]
+async fn fk_labels() {
+    let generator = get_generator(GeneratorType::PlantUML).unwrap();
+    let result = generator
+        .generate(load_erd().await, &opts(Direction::TB))
+        .unwrap();
+    assert!(result.contains("order_detail }o--|| sku : order_detail_sku_id_fkey\n"));
+    assert!(result.contains(
+        "order_detail }o--|| customer_order : order_detail_customer_order_id_fkey\\nON DELETE CASCADE\n"
+    ));
+    assert!(result.contains(
+        "order_detail_approval |o--|| order_detail : order_detail_approval_order_detail_id_customer_order_id_fkey\\nON UPDATE CASCADE\\nDEFERRABLE INITIALLY DEFERRED\n"
+    ));
+
+    let result = generator
+        .generate(
+            load_erd().await,
+            &GeneratorConfigOptions {
+                highlight_cascades: true,
+                ..opts(Direction::TB)
+            },
+        )
+        .unwrap();
+    assert!(result.contains("order_detail }o-[#C0392B]-|| customer_order : "));
+    assert!(result.contains("order_detail }o--|| sku : "));
+}
+
+#[tokio::test]
+async fn puml_lib() {
+    let generator = get_generator(GeneratorType::PlantUML).unwrap();
+    let included = generator
+        .generate(load_erd().await, &GeneratorConfigOptions::default())
+        .unwrap();
+    let url = included
+        .lines()
+        .find_map(|line| line.strip_prefix("!include "))
+        .unwrap();
+    let (tag, path) = url
+        .strip_prefix("https://raw.githubusercontent.com/kurotych/sqlant/")
+        .and_then(|rest| rest.split_once('/'))
+        .unwrap();
+    let lib = std::fs::read_to_string(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(path))
+        .unwrap();
+    // Included library is the same as the bundled one (tags have to be fetched)
+    let output = std::process::Command::new("git")
+        .args(["show", &format!("{tag}:{path}")])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{tag} isn't found in repository");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), lib);
+    assert!(lib.contains("$uq=false"));
+
+    let inlined = generator
+        .generate(
+            load_erd().await,
+            &GeneratorConfigOptions {
+                inline_puml_lib: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert!(inlined.contains(&lib));
+    assert!(!inlined.contains("!include"));
+}
@@ -1,4 +1,7 @@
-use sqlant::{lookup_loader, sql_entities::ColumnConstraints::*, sql_entities::*};
+use sqlant::{
+    lookup_loader, sql_entities::ColumnConstraints::*, sql_entities::*, table_filter::TableFilter,
+    tls::TlsConfig,
+};
 use std::{collections::BTreeMap, env};
 
 mod utils;
@@ -6,9 +9,14 @@ use crate::utils::check_fk;
 
 async fn load_erd() -> SqlERData {
     let con_string = env::var("CON_STRING").unwrap();
-    let mut parser = lookup_loader(&con_string, "test_schema".to_string())
-        .await
-        .unwrap();
+    let mut parser = lookup_loader(
+        &con_string,
+        vec!["test_schema".to_string()],
+        TableFilter::default(),
+        TlsConfig::default(),
+    )
+    .await
+    .unwrap();
     parser.load_erd_data().await.unwrap()
 }
 
@@ -20,7 +28,7 @@ async fn custom_schema_columns() {
             "customers",
             vec![
                 ("customer_id", "integer", vec![PrimaryKey, NotNull, Unique]),
-                ("customer_name", "character varying", vec![]),
+                ("customer_name", "character varying", vec![Unique]),
             ],
         ),
         (
@@ -29,6 +37,7 @@ async fn custom_schema_columns() {
                 ("order_id", "integer", vec![PrimaryKey, NotNull, Unique]),
                 ("order_description", "character varying", vec![]),
                 ("customer_id", "integer", vec![ForeignKey]),
+                ("customer_name", "character varying", vec![ForeignKey]),
             ],
         ),
     ]);
@@ -70,5 +79,5 @@ async fn custom_schema_fks() {
 async fn tables_data() {
     let sql_er_data: SqlERData = load_erd().await;
     assert_eq!(sql_er_data.tables.len(), 2);
-    assert_eq!(sql_er_data.foreign_keys.len(), 1);
+    assert_eq!(sql_er_data.foreign_keys.len(), 2);
 }
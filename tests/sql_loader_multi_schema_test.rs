@@ -0,0 +1,185 @@
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader, get_generator, lookup_loader, sql_entities::*,
+    table_filter::TableFilter, tls::TlsConfig, GeneratorConfigOptions, GeneratorType,
+};
+use std::{collections::BTreeMap, env};
+
+mod utils;
+use crate::utils::check_fk;
+
+async fn load_erd() -> SqlERData {
+    let con_string = env::var("CON_STRING").unwrap();
+    let mut parser = lookup_loader(
+        &con_string,
+        vec!["public".to_string(), "billing".to_string()],
+        TableFilter::default(),
+        TlsConfig::default(),
+    )
+    .await
+    .unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+async fn load_ddl_erd(schema_names: Vec<String>) -> SqlERData {
+    let mut parser = DdlERDLoader::from_files(
+        &["tests/test_db.sql", "tests/test_multi_schema.sql"],
+        schema_names,
+    )
+    .unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+fn generate(sql_er_data: SqlERData, generator_type: GeneratorType) -> String {
+    get_generator(generator_type)
+        .unwrap()
+        .generate(sql_er_data, &GeneratorConfigOptions::default())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn qualified_names() {
+    let sql_er_data: SqlERData = load_erd().await;
+    let invoice = sql_er_data
+        .tables
+        .iter()
+        .find(|t| t.name == "billing.invoice")
+        .unwrap();
+    assert_eq!(invoice.schema.as_deref(), Some("billing"));
+    assert!(sql_er_data
+        .tables
+        .iter()
+        .filter(|t| t.name != "billing.invoice")
+        .all(|t| t.name.starts_with("public.") && t.schema.as_deref() == Some("public")));
+    assert!(sql_er_data
+        .views
+        .iter()
+        .any(|v| v.name == "billing.unpaid_invoice"
+            && v.schema.as_deref() == Some("billing")
+            && v.depends_on == vec!["billing.invoice"]));
+    assert_eq!(
+        sql_er_data.schemas().into_iter().collect::<Vec<_>>(),
+        vec!["billing", "public"]
+    );
+}
+
+#[tokio::test]
+async fn qualified_enums() {
+    let sql_er_data: SqlERData = load_erd().await;
+    assert_eq!(
+        sql_er_data.enums.keys().collect::<Vec<_>>(),
+        vec!["billing.product_category", "public.product_category"]
+    );
+    let category = |table_name: &str| {
+        let table = sql_er_data
+            .tables
+            .iter()
+            .find(|t| t.name == table_name)
+            .unwrap();
+        let col = table.columns.iter().find(|c| c.name == "category").unwrap();
+        col.datatype.clone()
+    };
+    assert_eq!(category("billing.invoice"), "billing.product_category");
+    assert_eq!(category("public.product"), "public.product_category");
+
+    let ddl_er_data = load_ddl_erd(vec!["public".to_string(), "billing".to_string()]).await;
+    assert_eq!(ddl_er_data.enums, sql_er_data.enums);
+}
+
+#[tokio::test]
+async fn cross_schema_fks() {
+    let sql_er_data: SqlERData = load_erd().await;
+    check_fk(
+        &sql_er_data,
+        "billing.invoice",
+        "public.customer_order",
+        vec!["customer_order_id"],
+        vec!["id"],
+    );
+    check_fk(
+        &sql_er_data,
+        "public.customer_order",
+        "public.customer",
+        vec!["customer_id"],
+        vec!["id"],
+    );
+}
+
+#[tokio::test]
+async fn view_dependencies() {
+    let sql_er_data: SqlERData = load_erd().await;
+    let view = sql_er_data
+        .views
+        .iter()
+        .find(|v| v.name == "billing.unpaid_invoice_month")
+        .unwrap();
+    // View of view and materialized view of another schema
+    assert_eq!(
+        view.depends_on,
+        vec!["billing.unpaid_invoice", "public.monthly_sales_summary"]
+    );
+    assert_eq!(
+        view.used_columns,
+        BTreeMap::from([
+            ("billing.unpaid_invoice".to_string(), vec!["id".to_string()]),
+            (
+                "public.monthly_sales_summary".to_string(),
+                vec!["month".to_string()]
+            ),
+        ])
+    );
+
+    // The same dependencies, but columns aren't resolved without database
+    let ddl_er_data = load_ddl_erd(vec!["public".to_string(), "billing".to_string()]).await;
+    let ddl_view = ddl_er_data
+        .views
+        .iter()
+        .find(|v| v.name == "billing.unpaid_invoice_month")
+        .unwrap();
+    assert_eq!(ddl_view.depends_on, view.depends_on);
+    assert!(ddl_view.used_columns.is_empty());
+}
+
+#[tokio::test]
+async fn all_schemas() {
+    let con_string = env::var("CON_STRING").unwrap();
+    let mut parser = lookup_loader(
+        &con_string,
+        vec!["*".to_string()],
+        TableFilter::default(),
+        TlsConfig::default(),
+    )
+    .await
+    .unwrap();
+    let sql_er_data = parser.load_erd_data().await.unwrap();
+    let schemas = sql_er_data.schemas();
+    for schema in ["billing", "public", "test_schema"] {
+        assert!(schemas.contains(schema));
+    }
+}
+
+#[tokio::test]
+async fn ddl_loader_matches_database() {
+    let expected = generate(load_erd().await, GeneratorType::PlantUML);
+    let ddl_er_data = load_ddl_erd(vec!["public".to_string(), "billing".to_string()]).await;
+    assert_eq!(generate(ddl_er_data, GeneratorType::PlantUML), expected);
+
+    // Unqualified objects belong to `public`
+    let ddl_er_data = load_ddl_erd(vec!["*".to_string()]).await;
+    assert_eq!(generate(ddl_er_data, GeneratorType::PlantUML), expected);
+}
+
+#[tokio::test]
+async fn plantuml_packages() {
+    let result = generate(load_erd().await, GeneratorType::PlantUML);
+    assert!(result.contains("set namespaceSeparator none"));
+    assert!(result.contains("package billing {\ntable(billing.invoice) {"));
+    assert!(result.contains("package public {\n"));
+    assert!(result.contains("billing.invoice }o--|| public.customer_order"));
+}
+
+#[tokio::test]
+async fn mermaid_quoted_names() {
+    let result = generate(load_erd().await, GeneratorType::Mermaid);
+    assert!(result.contains("\"billing.invoice\" {"));
+    assert!(result.contains("\"billing.invoice\" }o--|| \"public.customer_order\""));
+}
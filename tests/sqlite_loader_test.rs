@@ -0,0 +1,262 @@
+use sqlant::{
+    lookup_loader, sql_entities::ColumnConstraints::*, sql_entities::*, table_filter::TableFilter,
+    tls::TlsConfig, SqlantError,
+};
+use std::collections::BTreeMap;
+
+mod utils;
+use crate::utils::check_fk;
+
+async fn load_erd() -> SqlERData {
+    load_erd_from_sql(include_str!("test_db_sqlite.sql")).await
+}
+
+async fn load_erd_from_sql(sql: &str) -> SqlERData {
+    let db_file = tempfile::NamedTempFile::new().unwrap();
+    let con = rusqlite::Connection::open(db_file.path()).unwrap();
+    con.execute_batch(sql).unwrap();
+    drop(con);
+
+    let con_string = format!("sqlite://{}", db_file.path().display());
+    let mut parser = lookup_loader(
+        &con_string,
+        vec!["public".to_string()],
+        TableFilter::default(),
+        TlsConfig::default(),
+    )
+    .await
+    .unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+#[tokio::test]
+async fn columns() {
+    let sql_er_data: SqlERData = load_erd().await;
+    let tables = BTreeMap::from([
+        (
+            "customer",
+            vec![
+                ("id", "integer", vec![PrimaryKey, NotNull, Unique]),
+                ("name", "text", vec![NotNull]),
+                ("email", "text", vec![NotNull, Unique]),
+                (
+                    "registered_at",
+                    "timestamp",
+                    vec![NotNull, Default("current_timestamp".into())],
+                ),
+            ],
+        ),
+        (
+            "product",
+            vec![
+                ("id", "integer", vec![PrimaryKey, NotNull, Unique]),
+                ("vendor_id", "integer", vec![ForeignKey, NotNull]),
+                ("name", "text", vec![NotNull, Index]),
+            ],
+        ),
+        (
+            "order_detail_approval",
+            vec![
+                (
+                    "order_detail_id",
+                    "integer",
+                    vec![PrimaryKey, ForeignKey, NotNull, Unique],
+                ),
+                (
+                    "customer_order_id",
+                    "integer",
+                    vec![PrimaryKey, ForeignKey, NotNull, Unique],
+                ),
+                ("approved_at", "timestamp", vec![NotNull]),
+            ],
+        ),
+    ]);
+
+    for (table_name, cols) in tables {
+        let table = sql_er_data
+            .tables
+            .iter()
+            .find(|&t| t.name == table_name)
+            .unwrap();
+        assert_eq!(cols.len(), table.columns.len());
+        for (exp_col_name, exp_col_type, exp_constraints) in cols {
+            let col = table
+                .columns
+                .iter()
+                .find(|col| col.name == exp_col_name)
+                .unwrap();
+            assert_eq!(col.constraints, exp_constraints.into_iter().collect());
+            assert_eq!(col.datatype, exp_col_type);
+        }
+    }
+}
+
+#[tokio::test]
+async fn fks() {
+    let sql_er_data: SqlERData = load_erd().await;
+    check_fk(
+        &sql_er_data,
+        "order_detail_approval",
+        "order_detail",
+        vec!["order_detail_id", "customer_order_id"],
+        vec!["id", "customer_order_id"],
+    );
+    check_fk(
+        &sql_er_data,
+        "order_detail",
+        "customer_order",
+        vec!["customer_order_id"],
+        vec!["id"],
+    );
+    // REFERENCES without column list points to PK
+    check_fk(
+        &sql_er_data,
+        "product",
+        "vendor",
+        vec!["vendor_id"],
+        vec!["id"],
+    );
+    check_fk(
+        &sql_er_data,
+        "vendor_address",
+        "vendor",
+        vec!["vendor_id"],
+        vec!["id"],
+    );
+}
+
+#[tokio::test]
+async fn fk_actions() {
+    let sql_er_data: SqlERData = load_erd().await;
+    let fk = |source_table: &str, target_table: &str| {
+        sql_er_data
+            .foreign_keys
+            .iter()
+            .find(|fk| fk.source_table.name == source_table && fk.target_table.name == target_table)
+            .unwrap()
+    };
+    assert_eq!(
+        fk("order_detail", "customer_order").clauses(),
+        vec!["ON DELETE CASCADE"]
+    );
+    assert_eq!(
+        fk("order_detail_approval", "order_detail").clauses(),
+        vec!["ON UPDATE CASCADE"]
+    );
+    assert!(fk("order_detail", "product").clauses().is_empty());
+}
+
+#[tokio::test]
+async fn fks_case_insensitive() {
+    let sql_er_data = load_erd_from_sql(
+        "CREATE TABLE Customer (Id integer PRIMARY KEY);
+        CREATE TABLE customer_order (
+          id integer PRIMARY KEY,
+          customer_id integer REFERENCES customer (id)
+        );",
+    )
+    .await;
+    check_fk(
+        &sql_er_data,
+        "customer_order",
+        "Customer",
+        vec!["customer_id"],
+        vec!["Id"],
+    );
+}
+
+#[tokio::test]
+async fn fks_to_missing_tables() {
+    // SQLite allows FKs to tables that don't exist, they are skipped
+    let sql_er_data = load_erd_from_sql(
+        "CREATE TABLE customer (id integer PRIMARY KEY);
+        CREATE TABLE customer_order (
+          id integer PRIMARY KEY,
+          customer_id integer REFERENCES customer (id),
+          coupon_id integer REFERENCES coupon (id)
+        );",
+    )
+    .await;
+    assert_eq!(sql_er_data.tables.len(), 2);
+    assert_eq!(sql_er_data.foreign_keys.len(), 1);
+    check_fk(
+        &sql_er_data,
+        "customer_order",
+        "customer",
+        vec!["customer_id"],
+        vec!["id"],
+    );
+    let customer_order = &sql_er_data.foreign_keys[0].source_table;
+    // FK points to the table with marked columns
+    assert!(std::sync::Arc::ptr_eq(
+        customer_order,
+        &sql_er_data.tables[1]
+    ));
+    for (col_name, is_fk) in [("customer_id", true), ("coupon_id", false)] {
+        let col = customer_order
+            .columns
+            .iter()
+            .find(|c| c.name == col_name)
+            .unwrap();
+        assert_eq!(col.is_fk(), is_fk);
+    }
+}
+
+#[tokio::test]
+async fn tables_data() {
+    let sql_er_data: SqlERData = load_erd().await;
+    assert_eq!(sql_er_data.tables.len(), 7);
+    assert_eq!(sql_er_data.foreign_keys.len(), 6);
+    assert!(sql_er_data.enums.is_empty());
+}
+
+#[tokio::test]
+async fn views() {
+    let sql_er_data: SqlERData = load_erd().await;
+    assert_eq!(sql_er_data.views.len(), 1);
+    let view = &sql_er_data.views[0];
+    assert_eq!(view.name, "top_customers");
+    assert!(!view.materialized);
+    let columns: Vec<&str> = view.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(columns, vec!["customer_id", "total_orders"]);
+}
+
+#[tokio::test]
+async fn is_zero_one_to_one() {
+    let sql_er_data: SqlERData = load_erd().await;
+    for fk in &sql_er_data.foreign_keys {
+        if fk.source_table.name == "vendor_address"
+            || fk.source_table.name == "order_detail_approval"
+        {
+            assert!(fk.is_zero_one_to_one);
+        } else {
+            assert!(!fk.is_zero_one_to_one);
+        }
+    }
+}
+
+#[tokio::test]
+async fn composite_pk() {
+    let sql_er_data: SqlERData = load_erd().await;
+    for tbl in &sql_er_data.tables {
+        if tbl.name == "order_detail_approval" || tbl.name == "order_detail" {
+            assert!(tbl.has_composite_pk);
+        } else {
+            assert!(!tbl.has_composite_pk);
+        }
+    }
+}
+
+#[tokio::test]
+async fn missing_file() {
+    assert!(matches!(
+        lookup_loader(
+            "sqlite:///nonexistent/dir/db.sqlite",
+            vec!["public".to_string()],
+            TableFilter::default(),
+            TlsConfig::default(),
+        )
+        .await,
+        Err(SqlantError::Connection(_))
+    ));
+}
@@ -0,0 +1,227 @@
+use sqlant::{
+    ddl_erd_loader::DdlERDLoader,
+    lookup_loader,
+    sql_entities::*,
+    table_filter::{FilteredERDLoader, TableFilter},
+    tls::TlsConfig,
+};
+use std::env;
+
+mod utils;
+use crate::utils::check_fk;
+
+async fn load_erd(schema_names: &[&str], include: &[&str], exclude: &[&str]) -> SqlERData {
+    let con_string = env::var("CON_STRING").unwrap();
+    let schema_names = schema_names.iter().map(ToString::to_string).collect();
+    let table_filter = TableFilter::new(include, exclude).unwrap();
+    let mut parser = lookup_loader(
+        &con_string,
+        schema_names,
+        table_filter,
+        TlsConfig::default(),
+    )
+    .await
+    .unwrap();
+    parser.load_erd_data().await.unwrap()
+}
+
+async fn load_ddl_erd(include: &[&str], exclude: &[&str]) -> SqlERData {
+    let mut parser = FilteredERDLoader::new(
+        DdlERDLoader::from_files(&["tests/test_db.sql"], vec!["public".to_string()]).unwrap(),
+        TableFilter::new(include, exclude).unwrap(),
+    );
+    parser.load_erd_data().await.unwrap()
+}
+
+fn names(sql_er_data: &SqlERData) -> (Vec<&str>, Vec<&str>) {
+    let mut tables: Vec<&str> = sql_er_data.tables.iter().map(|t| t.name.as_str()).collect();
+    tables.sort();
+    let mut views: Vec<&str> = sql_er_data.views.iter().map(|v| v.name.as_str()).collect();
+    views.sort();
+    (tables, views)
+}
+
+fn dependencies(sql_er_data: &SqlERData) -> Vec<(&str, &str)> {
+    let mut res: Vec<(&str, &str)> = sql_er_data
+        .views
+        .iter()
+        .flat_map(|v| v.depends_on.iter().map(|d| (v.name.as_str(), d.as_str())))
+        .collect();
+    res.sort();
+    res
+}
+
+fn fks(sql_er_data: &SqlERData) -> Vec<(&str, &str)> {
+    let mut fks: Vec<(&str, &str)> = sql_er_data
+        .foreign_keys
+        .iter()
+        .map(|fk| (fk.source_table.name.as_str(), fk.target_table.name.as_str()))
+        .collect();
+    fks.sort();
+    fks
+}
+
+#[test]
+fn is_match() {
+    let table_filter = TableFilter::new(&["order_*", "billing.*"], &["*_approval"]).unwrap();
+    assert!(table_filter.is_match(None, "order_detail"));
+    assert!(!table_filter.is_match(None, "order_detail_approval"));
+    assert!(!table_filter.is_match(None, "customer"));
+    assert!(table_filter.is_match(Some("billing"), "invoice"));
+    assert!(!table_filter.is_match(Some("public"), "invoice"));
+
+    let table_filter = TableFilter::default();
+    assert!(table_filter.is_empty());
+    assert!(table_filter.is_match(Some("public"), "customer"));
+
+    assert!(TableFilter::new(&["order_["], &[]).is_err());
+}
+
+#[tokio::test]
+async fn exclude() {
+    let sql_er_data = load_erd(&["public"], &[], &["vendor*", "*_summary"]).await;
+    let (tables, views) = names(&sql_er_data);
+    assert_eq!(
+        tables,
+        vec![
+            "customer",
+            "customer_order",
+            "order_detail",
+            "order_detail_approval",
+            "product",
+            "sku"
+        ]
+    );
+    assert_eq!(views, vec!["top_customers"]);
+    // product -> vendor and vendor_address -> vendor are dropped
+    assert_eq!(
+        fks(&sql_er_data),
+        vec![
+            ("customer_order", "customer"),
+            ("order_detail", "customer_order"),
+            ("order_detail", "sku"),
+            ("order_detail_approval", "order_detail"),
+            ("sku", "product"),
+        ]
+    );
+    // Column of dropped FK isn't marked as FK
+    let product = sql_er_data
+        .tables
+        .iter()
+        .find(|t| t.name == "product")
+        .unwrap();
+    assert!(!product
+        .columns
+        .iter()
+        .find(|c| c.name == "vendor_id")
+        .unwrap()
+        .is_fk());
+}
+
+#[tokio::test]
+async fn include() {
+    let sql_er_data = load_erd(&["public"], &["order_*", "customer_order"], &["*_approval"]).await;
+    let (tables, views) = names(&sql_er_data);
+    assert_eq!(tables, vec!["customer_order", "order_detail"]);
+    assert!(views.is_empty());
+    assert_eq!(fks(&sql_er_data), vec![("order_detail", "customer_order")]);
+    check_fk(
+        &sql_er_data,
+        "order_detail",
+        "customer_order",
+        vec!["customer_order_id"],
+        vec!["id"],
+    );
+}
+
+#[tokio::test]
+async fn qualified_patterns() {
+    let sql_er_data = load_erd(&["public", "billing"], &["billing.*", "*.customer*"], &[]).await;
+    let (tables, views) = names(&sql_er_data);
+    assert_eq!(
+        tables,
+        vec![
+            "billing.invoice",
+            "public.customer",
+            "public.customer_order"
+        ]
+    );
+    assert_eq!(
+        views,
+        vec!["billing.unpaid_invoice", "billing.unpaid_invoice_month"]
+    );
+    assert_eq!(
+        fks(&sql_er_data),
+        vec![
+            ("billing.invoice", "public.customer_order"),
+            ("public.customer_order", "public.customer"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn ddl_loader_matches_db() {
+    for (include, exclude) in [
+        (vec![], vec!["vendor*", "*_summary"]),
+        (vec!["order_*", "customer_order"], vec!["*_approval"]),
+    ] {
+        let sql_er_data = load_erd(&["public"], &include, &exclude).await;
+        let ddl_sql_er_data = load_ddl_erd(&include, &exclude).await;
+        assert_eq!(names(&sql_er_data), names(&ddl_sql_er_data));
+        assert_eq!(fks(&sql_er_data), fks(&ddl_sql_er_data));
+        assert_eq!(dependencies(&sql_er_data), dependencies(&ddl_sql_er_data));
+    }
+}
+
+#[tokio::test]
+async fn view_dependencies() {
+    let sql_er_data = load_erd(&["public"], &[], &["vendor*"]).await;
+    assert_eq!(
+        dependencies(&sql_er_data),
+        vec![
+            ("monthly_sales_summary", "customer_order"),
+            ("top_customers", "customer_order"),
+        ]
+    );
+    // Dependencies on filtered out tables are dropped
+    for sql_er_data in [
+        load_erd(&["public"], &[], &["customer_order"]).await,
+        load_ddl_erd(&[], &["customer_order"]).await,
+    ] {
+        assert_eq!(names(&sql_er_data).1.len(), 2);
+        assert!(dependencies(&sql_er_data).is_empty());
+    }
+}
+
+#[tokio::test]
+async fn fk_columns_of_filtered_out_tables() {
+    let is_fk = |sql_er_data: &SqlERData, table_name: &str, column_name: &str| {
+        let tbl = sql_er_data
+            .tables
+            .iter()
+            .find(|t| t.name == table_name)
+            .unwrap();
+        tbl.columns
+            .iter()
+            .find(|c| c.name == column_name)
+            .unwrap()
+            .is_fk()
+    };
+    for sql_er_data in [
+        load_erd(&["public"], &[], &["sku"]).await,
+        load_ddl_erd(&[], &["sku"]).await,
+    ] {
+        assert!(!is_fk(&sql_er_data, "order_detail", "sku_id"));
+        assert!(is_fk(&sql_er_data, "order_detail", "customer_order_id"));
+        // FKs point to the tables with unmarked columns
+        for fk in &sql_er_data.foreign_keys {
+            assert!(sql_er_data
+                .tables
+                .iter()
+                .any(|tbl| std::sync::Arc::ptr_eq(tbl, &fk.source_table)));
+        }
+    }
+    // Target is in schema that isn't loaded
+    let sql_er_data = load_erd(&["billing"], &[], &[]).await;
+    assert!(!is_fk(&sql_er_data, "invoice", "customer_order_id"));
+}
@@ -16,6 +16,7 @@ create table customer (
   , phone_number text not null
   , registered_at timestamp with time zone not null
 );
+CREATE INDEX customer_lower_name_idx ON customer (lower(name));
 COMMENT ON TABLE customer IS 'Customer Information';
 COMMENT ON COLUMN customer.name IS 'Customer Name';
 COMMENT ON COLUMN customer.zip_code IS 'Customer Zip Code';
@@ -25,8 +26,9 @@ COMMENT ON COLUMN customer.phone_number IS 'Customer Phone Number';
 create table vendor (
   id bigserial primary key
   , name text not null
-  , phone_number text not null
+  , phone_number text not null unique
 );
+CREATE INDEX vendor_name_idx ON vendor USING hash (name);
 
 create table vendor_address (
   vendor_id bigint primary key
@@ -48,6 +50,7 @@ create table product (
   , category product_category not null
   , FOREIGN KEY(vendor_id) REFERENCES vendor (id)
 );
+CREATE INDEX product_name_idx ON product (name);
 
 create table sku (
   id bigserial primary key
@@ -58,6 +61,7 @@ create table sku (
   , sales_unit_price numeric not null
   , purchase_unit_price numeric not null
   , FOREIGN KEY(product_id) REFERENCES product (id)
+  , UNIQUE(product_id, color, size)
 );
 
 create table customer_order (
@@ -67,21 +71,24 @@ create table customer_order (
   , shipping_address text not null
   , payment_method text not null
   , total_price numeric not null
-  , total_tax_amount numeric not null
+  , total_tax_amount numeric not null default 0
   , ordered_at timestamp with time zone not null
   , FOREIGN KEY(customer_id) REFERENCES customer (id)
+  , CHECK (total_tax_amount <= total_price)
 );
+CREATE INDEX customer_order_ordered_at_idx ON customer_order (customer_id, ordered_at)
+  INCLUDE (total_price) WHERE payment_method = 'card';
 
 create table order_detail (
   id bigserial not null
   , customer_order_id bigint not null
   , sku_id bigint not null
-  , amount bigint not null
+  , amount bigint not null check (amount > 0)
   , price_before_tax numeric not null
   , price_after_tax numeric not null
   , ordered_at timestamp with time zone not null
   , PRIMARY KEY(id, customer_order_id)
-  , FOREIGN KEY(customer_order_id) REFERENCES customer_order (id)
+  , FOREIGN KEY(customer_order_id) REFERENCES customer_order (id) ON DELETE CASCADE
   , FOREIGN KEY(sku_id) REFERENCES sku (id)
 );
 
@@ -92,6 +99,7 @@ create table order_detail_approval (
   , approved_at timestamp with time zone not null
   , PRIMARY KEY(order_detail_id, customer_order_id)
   , FOREIGN KEY(order_detail_id, customer_order_id) REFERENCES order_detail (id, customer_order_id)
+    ON UPDATE CASCADE DEFERRABLE INITIALLY DEFERRED
 );
 
 CREATE VIEW top_customers AS
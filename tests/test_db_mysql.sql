@@ -0,0 +1,70 @@
+drop table if exists order_detail_approval;
+drop table if exists order_detail;
+drop table if exists customer_order;
+drop table if exists product;
+drop table if exists vendor_address;
+drop table if exists vendor;
+drop table if exists customer;
+drop view if exists top_customers;
+
+create table customer (
+  id bigint auto_increment primary key
+  , name text not null
+  , email varchar(255) not null unique
+  , registered_at timestamp not null
+);
+
+create table vendor (
+  id bigint auto_increment primary key
+  , name text not null
+);
+
+create table vendor_address (
+  vendor_id bigint primary key
+  , city text not null
+  , line1 text
+  , FOREIGN KEY(vendor_id) REFERENCES vendor (id)
+);
+
+create table product (
+  id bigint auto_increment primary key
+  , vendor_id bigint not null
+  , name text not null
+  , category enum('electronics', 'jewelry', 'home') not null
+  , FOREIGN KEY(vendor_id) REFERENCES vendor (id)
+);
+
+create table customer_order (
+  id bigint auto_increment primary key
+  , customer_id bigint not null
+  , total_price decimal(10, 2) not null
+  , ordered_at timestamp not null
+  , FOREIGN KEY(customer_id) REFERENCES customer (id)
+);
+
+create table order_detail (
+  id bigint not null
+  , customer_order_id bigint not null
+  , product_id bigint not null
+  , amount bigint not null
+  , PRIMARY KEY(id, customer_order_id)
+  , FOREIGN KEY(customer_order_id) REFERENCES customer_order (id) ON DELETE CASCADE
+  , FOREIGN KEY(product_id) REFERENCES product (id)
+);
+
+create table order_detail_approval (
+  order_detail_id bigint not null
+  , customer_order_id bigint not null
+  , approved_at timestamp not null
+  , PRIMARY KEY(order_detail_id, customer_order_id)
+  , FOREIGN KEY(order_detail_id, customer_order_id) REFERENCES order_detail (id, customer_order_id) ON UPDATE CASCADE
+);
+
+CREATE VIEW top_customers AS
+SELECT
+  co.customer_id,
+  COUNT(co.id) AS total_orders
+FROM
+  customer_order co
+GROUP BY
+  co.customer_id;
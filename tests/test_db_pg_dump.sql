@@ -0,0 +1,569 @@
+--
+-- PostgreSQL database dump
+--
+
+\restrict DwWPrSyPu5P7AiB6rOmleY6f7xNpc1AdNYNFWtEZCEdlcFrfoPAnwgKkXaoxmba
+
+-- Dumped from database version 15.18 (Debian 15.18-0+deb12u1)
+-- Dumped by pg_dump version 15.18 (Debian 15.18-0+deb12u1)
+
+SET statement_timeout = 0;
+SET lock_timeout = 0;
+SET idle_in_transaction_session_timeout = 0;
+SET client_encoding = 'SQL_ASCII';
+SET standard_conforming_strings = on;
+SELECT pg_catalog.set_config('search_path', '', false);
+SET check_function_bodies = false;
+SET xmloption = content;
+SET client_min_messages = warning;
+SET row_security = off;
+
+--
+-- Name: product_category; Type: TYPE; Schema: public; Owner: sql
+--
+
+CREATE TYPE public.product_category AS ENUM (
+    'electronics',
+    'jewelry',
+    'home'
+);
+
+
+ALTER TYPE public.product_category OWNER TO sql;
+
+SET default_tablespace = '';
+
+SET default_table_access_method = heap;
+
+--
+-- Name: customer; Type: TABLE; Schema: public; Owner: sql
+--
+
+CREATE TABLE public.customer (
+    id bigint NOT NULL,
+    name text NOT NULL,
+    zip_code text NOT NULL,
+    address text NOT NULL,
+    phone_number text NOT NULL,
+    registered_at timestamp with time zone NOT NULL
+);
+
+
+ALTER TABLE public.customer OWNER TO sql;
+
+--
+-- Name: TABLE customer; Type: COMMENT; Schema: public; Owner: sql
+--
+
+COMMENT ON TABLE public.customer IS 'Customer Information';
+
+
+--
+-- Name: COLUMN customer.name; Type: COMMENT; Schema: public; Owner: sql
+--
+
+COMMENT ON COLUMN public.customer.name IS 'Customer Name';
+
+
+--
+-- Name: COLUMN customer.zip_code; Type: COMMENT; Schema: public; Owner: sql
+--
+
+COMMENT ON COLUMN public.customer.zip_code IS 'Customer Zip Code';
+
+
+--
+-- Name: COLUMN customer.address; Type: COMMENT; Schema: public; Owner: sql
+--
+
+COMMENT ON COLUMN public.customer.address IS 'Customer Address';
+
+
+--
+-- Name: COLUMN customer.phone_number; Type: COMMENT; Schema: public; Owner: sql
+--
+
+COMMENT ON COLUMN public.customer.phone_number IS 'Customer Phone Number';
+
+
+--
+-- Name: customer_id_seq; Type: SEQUENCE; Schema: public; Owner: sql
+--
+
+CREATE SEQUENCE public.customer_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+ALTER TABLE public.customer_id_seq OWNER TO sql;
+
+--
+-- Name: customer_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: sql
+--
+
+ALTER SEQUENCE public.customer_id_seq OWNED BY public.customer.id;
+
+
+--
+-- Name: customer_order; Type: TABLE; Schema: public; Owner: sql
+--
+
+CREATE TABLE public.customer_order (
+    id bigint NOT NULL,
+    customer_id bigint NOT NULL,
+    delivery_method text NOT NULL,
+    shipping_address text NOT NULL,
+    payment_method text NOT NULL,
+    total_price numeric NOT NULL,
+    total_tax_amount numeric DEFAULT 0 NOT NULL,
+    ordered_at timestamp with time zone NOT NULL,
+    CONSTRAINT customer_order_check CHECK ((total_tax_amount <= total_price))
+);
+
+
+ALTER TABLE public.customer_order OWNER TO sql;
+
+--
+-- Name: customer_order_id_seq; Type: SEQUENCE; Schema: public; Owner: sql
+--
+
+CREATE SEQUENCE public.customer_order_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+ALTER TABLE public.customer_order_id_seq OWNER TO sql;
+
+--
+-- Name: customer_order_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: sql
+--
+
+ALTER SEQUENCE public.customer_order_id_seq OWNED BY public.customer_order.id;
+
+
+--
+-- Name: monthly_sales_summary; Type: MATERIALIZED VIEW; Schema: public; Owner: sql
+--
+
+CREATE MATERIALIZED VIEW public.monthly_sales_summary AS
+ SELECT (date_trunc('month'::text, customer_order.ordered_at))::date AS month,
+    count(*) AS total_orders,
+    sum(customer_order.total_price) AS total_spent
+   FROM public.customer_order
+  GROUP BY ((date_trunc('month'::text, customer_order.ordered_at))::date)
+  ORDER BY ((date_trunc('month'::text, customer_order.ordered_at))::date)
+  WITH NO DATA;
+
+
+ALTER TABLE public.monthly_sales_summary OWNER TO sql;
+
+--
+-- Name: order_detail; Type: TABLE; Schema: public; Owner: sql
+--
+
+CREATE TABLE public.order_detail (
+    id bigint NOT NULL,
+    customer_order_id bigint NOT NULL,
+    sku_id bigint NOT NULL,
+    amount bigint NOT NULL,
+    price_before_tax numeric NOT NULL,
+    price_after_tax numeric NOT NULL,
+    ordered_at timestamp with time zone NOT NULL,
+    CONSTRAINT order_detail_amount_check CHECK ((amount > 0))
+);
+
+
+ALTER TABLE public.order_detail OWNER TO sql;
+
+--
+-- Name: order_detail_approval; Type: TABLE; Schema: public; Owner: sql
+--
+
+CREATE TABLE public.order_detail_approval (
+    order_detail_id bigint NOT NULL,
+    customer_order_id bigint NOT NULL,
+    operator_id bigint NOT NULL,
+    approved_at timestamp with time zone NOT NULL
+);
+
+
+ALTER TABLE public.order_detail_approval OWNER TO sql;
+
+--
+-- Name: order_detail_id_seq; Type: SEQUENCE; Schema: public; Owner: sql
+--
+
+CREATE SEQUENCE public.order_detail_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+ALTER TABLE public.order_detail_id_seq OWNER TO sql;
+
+--
+-- Name: order_detail_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: sql
+--
+
+ALTER SEQUENCE public.order_detail_id_seq OWNED BY public.order_detail.id;
+
+
+--
+-- Name: product; Type: TABLE; Schema: public; Owner: sql
+--
+
+CREATE TABLE public.product (
+    id bigint NOT NULL,
+    vendor_id bigint NOT NULL,
+    name text NOT NULL,
+    country text NOT NULL,
+    category public.product_category NOT NULL
+);
+
+
+ALTER TABLE public.product OWNER TO sql;
+
+--
+-- Name: product_id_seq; Type: SEQUENCE; Schema: public; Owner: sql
+--
+
+CREATE SEQUENCE public.product_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+ALTER TABLE public.product_id_seq OWNER TO sql;
+
+--
+-- Name: product_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: sql
+--
+
+ALTER SEQUENCE public.product_id_seq OWNED BY public.product.id;
+
+
+--
+-- Name: sku; Type: TABLE; Schema: public; Owner: sql
+--
+
+CREATE TABLE public.sku (
+    id bigint NOT NULL,
+    product_id bigint NOT NULL,
+    color text NOT NULL,
+    size text NOT NULL,
+    weight numeric NOT NULL,
+    sales_unit_price numeric NOT NULL,
+    purchase_unit_price numeric NOT NULL
+);
+
+
+ALTER TABLE public.sku OWNER TO sql;
+
+--
+-- Name: sku_id_seq; Type: SEQUENCE; Schema: public; Owner: sql
+--
+
+CREATE SEQUENCE public.sku_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+ALTER TABLE public.sku_id_seq OWNER TO sql;
+
+--
+-- Name: sku_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: sql
+--
+
+ALTER SEQUENCE public.sku_id_seq OWNED BY public.sku.id;
+
+
+--
+-- Name: top_customers; Type: VIEW; Schema: public; Owner: sql
+--
+
+CREATE VIEW public.top_customers AS
+ SELECT co.customer_id,
+    count(co.id) AS total_orders,
+    sum(co.total_price) AS total_spent
+   FROM public.customer_order co
+  GROUP BY co.customer_id
+  ORDER BY (sum(co.total_price));
+
+
+ALTER TABLE public.top_customers OWNER TO sql;
+
+--
+-- Name: vendor; Type: TABLE; Schema: public; Owner: sql
+--
+
+CREATE TABLE public.vendor (
+    id bigint NOT NULL,
+    name text NOT NULL,
+    phone_number text NOT NULL
+);
+
+
+ALTER TABLE public.vendor OWNER TO sql;
+
+--
+-- Name: vendor_address; Type: TABLE; Schema: public; Owner: sql
+--
+
+CREATE TABLE public.vendor_address (
+    vendor_id bigint NOT NULL,
+    zip_code text NOT NULL,
+    state text NOT NULL,
+    city text NOT NULL,
+    line1 text NOT NULL,
+    line2 text
+);
+
+
+ALTER TABLE public.vendor_address OWNER TO sql;
+
+--
+-- Name: vendor_id_seq; Type: SEQUENCE; Schema: public; Owner: sql
+--
+
+CREATE SEQUENCE public.vendor_id_seq
+    START WITH 1
+    INCREMENT BY 1
+    NO MINVALUE
+    NO MAXVALUE
+    CACHE 1;
+
+
+ALTER TABLE public.vendor_id_seq OWNER TO sql;
+
+--
+-- Name: vendor_id_seq; Type: SEQUENCE OWNED BY; Schema: public; Owner: sql
+--
+
+ALTER SEQUENCE public.vendor_id_seq OWNED BY public.vendor.id;
+
+
+--
+-- Name: customer id; Type: DEFAULT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.customer ALTER COLUMN id SET DEFAULT nextval('public.customer_id_seq'::regclass);
+
+
+--
+-- Name: customer_order id; Type: DEFAULT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.customer_order ALTER COLUMN id SET DEFAULT nextval('public.customer_order_id_seq'::regclass);
+
+
+--
+-- Name: order_detail id; Type: DEFAULT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.order_detail ALTER COLUMN id SET DEFAULT nextval('public.order_detail_id_seq'::regclass);
+
+
+--
+-- Name: product id; Type: DEFAULT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.product ALTER COLUMN id SET DEFAULT nextval('public.product_id_seq'::regclass);
+
+
+--
+-- Name: sku id; Type: DEFAULT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.sku ALTER COLUMN id SET DEFAULT nextval('public.sku_id_seq'::regclass);
+
+
+--
+-- Name: vendor id; Type: DEFAULT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.vendor ALTER COLUMN id SET DEFAULT nextval('public.vendor_id_seq'::regclass);
+
+
+--
+-- Name: customer_order customer_order_pkey; Type: CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.customer_order
+    ADD CONSTRAINT customer_order_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: customer customer_pkey; Type: CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.customer
+    ADD CONSTRAINT customer_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: order_detail_approval order_detail_approval_pkey; Type: CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.order_detail_approval
+    ADD CONSTRAINT order_detail_approval_pkey PRIMARY KEY (order_detail_id, customer_order_id);
+
+
+--
+-- Name: order_detail order_detail_pkey; Type: CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.order_detail
+    ADD CONSTRAINT order_detail_pkey PRIMARY KEY (id, customer_order_id);
+
+
+--
+-- Name: product product_pkey; Type: CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.product
+    ADD CONSTRAINT product_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: sku sku_pkey; Type: CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.sku
+    ADD CONSTRAINT sku_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: sku sku_product_id_color_size_key; Type: CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.sku
+    ADD CONSTRAINT sku_product_id_color_size_key UNIQUE (product_id, color, size);
+
+
+--
+-- Name: vendor_address vendor_address_pkey; Type: CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.vendor_address
+    ADD CONSTRAINT vendor_address_pkey PRIMARY KEY (vendor_id);
+
+
+--
+-- Name: vendor vendor_phone_number_key; Type: CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.vendor
+    ADD CONSTRAINT vendor_phone_number_key UNIQUE (phone_number);
+
+
+--
+-- Name: vendor vendor_pkey; Type: CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.vendor
+    ADD CONSTRAINT vendor_pkey PRIMARY KEY (id);
+
+
+--
+-- Name: customer_lower_name_idx; Type: INDEX; Schema: public; Owner: sql
+--
+
+CREATE INDEX customer_lower_name_idx ON public.customer USING btree (lower(name));
+
+
+--
+-- Name: customer_order_ordered_at_idx; Type: INDEX; Schema: public; Owner: sql
+--
+
+CREATE INDEX customer_order_ordered_at_idx ON public.customer_order USING btree (customer_id, ordered_at) INCLUDE (total_price) WHERE (payment_method = 'card'::text);
+
+
+--
+-- Name: product_name_idx; Type: INDEX; Schema: public; Owner: sql
+--
+
+CREATE INDEX product_name_idx ON public.product USING btree (name);
+
+
+--
+-- Name: vendor_name_idx; Type: INDEX; Schema: public; Owner: sql
+--
+
+CREATE INDEX vendor_name_idx ON public.vendor USING hash (name);
+
+
+--
+-- Name: customer_order customer_order_customer_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.customer_order
+    ADD CONSTRAINT customer_order_customer_id_fkey FOREIGN KEY (customer_id) REFERENCES public.customer(id);
+
+
+--
+-- Name: order_detail_approval order_detail_approval_order_detail_id_customer_order_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.order_detail_approval
+    ADD CONSTRAINT order_detail_approval_order_detail_id_customer_order_id_fkey FOREIGN KEY (order_detail_id, customer_order_id) REFERENCES public.order_detail(id, customer_order_id) ON UPDATE CASCADE DEFERRABLE INITIALLY DEFERRED;
+
+
+--
+-- Name: order_detail order_detail_customer_order_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.order_detail
+    ADD CONSTRAINT order_detail_customer_order_id_fkey FOREIGN KEY (customer_order_id) REFERENCES public.customer_order(id) ON DELETE CASCADE;
+
+
+--
+-- Name: order_detail order_detail_sku_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.order_detail
+    ADD CONSTRAINT order_detail_sku_id_fkey FOREIGN KEY (sku_id) REFERENCES public.sku(id);
+
+
+--
+-- Name: product product_vendor_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.product
+    ADD CONSTRAINT product_vendor_id_fkey FOREIGN KEY (vendor_id) REFERENCES public.vendor(id);
+
+
+--
+-- Name: sku sku_product_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.sku
+    ADD CONSTRAINT sku_product_id_fkey FOREIGN KEY (product_id) REFERENCES public.product(id);
+
+
+--
+-- Name: vendor_address vendor_address_vendor_id_fkey; Type: FK CONSTRAINT; Schema: public; Owner: sql
+--
+
+ALTER TABLE ONLY public.vendor_address
+    ADD CONSTRAINT vendor_address_vendor_id_fkey FOREIGN KEY (vendor_id) REFERENCES public.vendor(id);
+
+
+--
+-- PostgreSQL database dump complete
+--
+
+\unrestrict DwWPrSyPu5P7AiB6rOmleY6f7xNpc1AdNYNFWtEZCEdlcFrfoPAnwgKkXaoxmba
+
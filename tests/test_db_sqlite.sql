@@ -0,0 +1,61 @@
+create table customer (
+  id integer primary key
+  , name text not null
+  , email text not null unique
+  , registered_at timestamp not null default current_timestamp
+);
+
+create table vendor (
+  id integer primary key
+  , name text not null
+);
+
+create table vendor_address (
+  vendor_id integer primary key
+  , city text not null
+  , line1 text
+  , FOREIGN KEY(vendor_id) REFERENCES vendor (id)
+);
+
+create table product (
+  id integer primary key
+  , vendor_id integer not null
+  , name text not null
+  , FOREIGN KEY(vendor_id) REFERENCES vendor
+);
+create index product_name_idx on product (name);
+
+create table customer_order (
+  id integer primary key
+  , customer_id integer not null
+  , total_price numeric not null
+  , ordered_at timestamp not null
+  , FOREIGN KEY(customer_id) REFERENCES customer (id)
+);
+
+create table order_detail (
+  id integer not null
+  , customer_order_id integer not null
+  , product_id integer not null
+  , amount integer not null
+  , PRIMARY KEY(id, customer_order_id)
+  , FOREIGN KEY(customer_order_id) REFERENCES customer_order (id) ON DELETE CASCADE
+  , FOREIGN KEY(product_id) REFERENCES product (id)
+);
+
+create table order_detail_approval (
+  order_detail_id integer not null
+  , customer_order_id integer not null
+  , approved_at timestamp not null
+  , PRIMARY KEY(order_detail_id, customer_order_id)
+  , FOREIGN KEY(order_detail_id, customer_order_id) REFERENCES order_detail (id, customer_order_id) ON UPDATE CASCADE
+);
+
+CREATE VIEW top_customers AS
+SELECT
+  co.customer_id,
+  COUNT(co.id) AS total_orders
+FROM
+  customer_order co
+GROUP BY
+  co.customer_id;
@@ -0,0 +1,20 @@
+CREATE SCHEMA billing;
+
+CREATE TABLE billing.invoice (
+  id bigserial PRIMARY KEY,
+  customer_order_id bigint NOT NULL REFERENCES public.customer_order(id),
+  amount numeric NOT NULL
+);
+
+CREATE VIEW billing.unpaid_invoice AS
+SELECT i.id, i.amount
+FROM billing.invoice i;
+
+-- View of view and materialized view of another schema
+CREATE VIEW billing.unpaid_invoice_month AS
+SELECT u.id, s.month
+FROM billing.unpaid_invoice u, public.monthly_sales_summary s;
+
+-- The same name as enum of public schema
+CREATE TYPE billing.product_category AS ENUM ('subscription', 'service');
+ALTER TABLE billing.invoice ADD COLUMN category billing.product_category;
@@ -12,3 +12,8 @@ CREATE TABLE test_schema.orders (
   FOREIGN KEY (customer_id) REFERENCES test_schema.customers(customer_id)
 );
 
+-- Unique index without constraint which is referenced by foreign key
+CREATE UNIQUE INDEX customers_customer_name_idx ON test_schema.customers (customer_name);
+
+ALTER TABLE test_schema.orders ADD COLUMN customer_name VARCHAR(50)
+  REFERENCES test_schema.customers(customer_name);
@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+# Generates certificates of TLS tests: CA, client certificate and key (PEM and PKCS#12).
+# Server certificate signed by the CA is written into $1 (default /tmp) for local Postgres:
+#   postgres -c ssl=on -c ssl_cert_file=/tmp/server.crt -c ssl_key_file=/tmp/server.key
+set -euo pipefail
+cd "$(dirname "$0")"
+SERVER_DIR=${1:-/tmp}
+
+openssl req -x509 -new -nodes -days 36500 -subj "/CN=sqlant test CA" \
+    -keyout ca.key -out ca.crt
+openssl req -new -nodes -subj "/CN=sql" -keyout client.key -out client.csr
+openssl x509 -req -days 36500 -in client.csr -CA ca.crt -CAkey ca.key -CAcreateserial -out client.crt
+openssl pkcs12 -export -in client.crt -inkey client.key -passout pass:sqlant -out client.p12
+
+openssl req -new -nodes -subj "/CN=localhost" \
+    -keyout "$SERVER_DIR/server.key" -out "$SERVER_DIR/server.csr"
+openssl x509 -req -days 36500 -in "$SERVER_DIR/server.csr" -CA ca.crt -CAkey ca.key \
+    -CAcreateserial -out "$SERVER_DIR/server.crt"
+chmod 600 "$SERVER_DIR/server.key"
+
+rm -f ca.key ca.srl client.csr "$SERVER_DIR/server.csr"